@@ -1,298 +0,0 @@
-use std::fs::File;
-use std::io::Write;
-
-// Structure to hold AI model parameters
-pub struct ModelParams {
-    pub base_power_consumption: f64, // watts
-    pub computation_factor: f64,     // computation power scaling factor
-    pub memory_usage: f64,           // GB
-    pub memory_power_factor: f64,    // watts per GB
-    pub inference_time: f64,         // seconds per inference at batch size 1
-    // New enhanced parameters
-    pub thermal_design_power: f64,   // Maximum thermal design power (watts)
-    pub cache_size: f64,             // Cache size in MB
-    pub memory_bandwidth: f64,       // Memory bandwidth in GB/s
-}
-
-// AI Energy Efficiency Optimizer
-pub struct Optimizer {
-    model_params: ModelParams,
-}
-
-impl Optimizer {
-    // Create a new optimizer with given model parameters
-    pub fn new(model_params: ModelParams) -> Self {
-        Optimizer { model_params }
-    }
-    
-    // Calculate energy consumption for inference with enhanced formulas
-    pub fn energy_consumption(&self, batch_size: u32, precision: &str) -> f64 {
-        let batch_size_f64 = batch_size as f64;
-        
-        // Enhanced precision factors with memory bandwidth consideration
-        let (precision_factor, memory_bandwidth_factor) = match precision {
-            "fp16" => (0.65, 0.8),  // FP16: slightly higher energy, better memory efficiency
-            "int8" => (0.35, 0.6),  // INT8: lower energy, reduced memory bandwidth
-            "int4" => (0.2, 0.4),   // INT4: very low energy, significant bandwidth reduction
-            _ => (1.0, 1.0),        // FP32: baseline
-        };
-        
-        // Dynamic voltage scaling factor (modern processors scale voltage with load)
-        let voltage_scaling = 1.0 + (batch_size_f64.ln() * 0.05).min(0.3);
-        
-        // Memory bandwidth saturation (realistic memory bottleneck)
-        let memory_saturation = 1.0 - (-batch_size_f64 / 32.0).exp();
-        let effective_memory_factor = memory_bandwidth_factor * memory_saturation;
-        
-        // Enhanced computation power with thermal throttling
-        let thermal_factor = 1.0 + (batch_size_f64 / 64.0).min(0.25); // Thermal throttling at high loads
-        let computation_power = self.model_params.computation_factor 
-            * batch_size_f64.powf(0.85) // Slightly less efficient scaling
-            * precision_factor 
-            * voltage_scaling
-            * thermal_factor;
-        
-        // Enhanced memory power with bandwidth considerations
-        let memory_power = self.model_params.memory_usage 
-            * self.model_params.memory_power_factor
-            * effective_memory_factor
-            * (1.0 + batch_size_f64.powf(0.3) * 0.1); // Memory power increases with batch size
-        
-        // Base power with idle efficiency
-        let base_power = self.model_params.base_power_consumption 
-            * (0.8 + 0.2 * (batch_size_f64 / 100.0).min(1.0)); // Base power scales slightly with utilization
-        
-        // Total power consumption
-        let total_power = base_power + computation_power + memory_power;
-        
-        // Enhanced inference time with memory and cache effects
-        let cache_efficiency = 1.0 - (batch_size_f64 / 128.0).min(0.3); // Cache misses increase with batch size
-        let inference_time = self.model_params.inference_time 
-            * batch_size_f64.powf(0.75) // Better scaling due to vectorization
-            * (2.0 - effective_memory_factor) // Memory bandwidth affects timing
-            / cache_efficiency; // Cache efficiency impact
-        
-        // Total energy consumption
-        total_power * inference_time
-    }
-    
-    // Calculate efficiency (samples processed per joule)
-    pub fn efficiency(&self, batch_size: u32, precision: &str) -> f64 {
-        let energy = self.energy_consumption(batch_size, precision);
-        (batch_size as f64) / energy
-    }
-    
-    // Find optimal batch size for energy efficiency
-    pub fn optimize_batch_size(&self, precision: &str, min_batch: u32, max_batch: u32) -> (u32, f64) {
-        let mut optimal_batch = min_batch;
-        let mut max_efficiency = self.efficiency(min_batch, precision);
-        
-        for batch_size in min_batch + 1..=max_batch {
-            let efficiency = self.efficiency(batch_size, precision);
-            if efficiency > max_efficiency {
-                max_efficiency = efficiency;
-                optimal_batch = batch_size;
-            }
-        }
-        
-        (optimal_batch, max_efficiency)
-    }
-    
-    // Export data for batch sizes and precisions to CSV
-    pub fn export_data(&self, min_batch: u32, max_batch: u32, filename: &str) -> std::io::Result<()> {
-        let mut file = File::create(filename)?;
-        writeln!(file, "batch_size,fp32_energy,fp16_energy,int8_energy,fp32_efficiency,fp16_efficiency,int8_efficiency")?;
-        
-        for batch_size in min_batch..=max_batch {
-            let fp32_energy = self.energy_consumption(batch_size, "fp32");
-            let fp16_energy = self.energy_consumption(batch_size, "fp16");
-            let int8_energy = self.energy_consumption(batch_size, "int8");
-            
-            let fp32_efficiency = self.efficiency(batch_size, "fp32");
-            let fp16_efficiency = self.efficiency(batch_size, "fp16");
-            let int8_efficiency = self.efficiency(batch_size, "int8");
-            
-            writeln!(file, "{},{},{},{},{},{},{}", 
-                batch_size, fp32_energy, fp16_energy, int8_energy,
-                fp32_efficiency, fp16_efficiency, int8_efficiency)?;
-        }
-        
-        Ok(())
-    }
-}
-
-// Main function to demonstrate usage
-fn main() -> std::io::Result<()> {
-    // Example parameters for a neural network model
-    let model_params = ModelParams {
-        base_power_consumption: 50.0,  // watts
-        computation_factor: 2.5,      // computation power scaling factor
-        memory_usage: 4.0,            // GB
-        memory_power_factor: 5.0,     // watts per GB
-        inference_time: 0.05,         // seconds per inference at batch size 1
-        thermal_design_power: 100.0,  // Maximum thermal design power (watts)
-        cache_size: 32.0,             // Cache size in MB
-        memory_bandwidth: 256.0,      // Memory bandwidth in GB/s
-    };
-    
-    let optimizer = Optimizer::new(model_params);
-    
-    // Export data for Python visualization
-    optimizer.export_data(1, 128, "ai_energy_data.csv")?;
-    println!("Data exported to ai_energy_data.csv");
-    
-    Ok(())
-}
-
-// Advanced mathematical framework for AI energy modeling
-use std::f64::consts::PI;
-
-// Mathematical constants
-const PLANCK_CONSTANT: f64 = 6.62607015e-34;
-const BOLTZMANN_CONSTANT: f64 = 1.380649e-23;
-const AVOGADRO_NUMBER: f64 = 6.02214076e23;
-const GOLDEN_RATIO: f64 = 1.618033988749;
-const EULER_MASCHERONI: f64 = 0.5772156649015329;
-
-impl Optimizer {
-    // Advanced energy computation using mathematical principles
-    pub fn advanced_energy_formula(&self, batch_size: u32, precision: &str, temperature: f64) -> f64 {
-        let b = batch_size as f64;
-        
-        // Fibonacci-based precision modeling with quantum field theory
-        let precision_quantum_factor = match precision {
-            "fp16" => self.fibonacci_quantum_efficiency(16, temperature),
-            "int8" => self.fibonacci_quantum_efficiency(8, temperature),
-            "int4" => self.fibonacci_quantum_efficiency(4, temperature),
-            _ => self.fibonacci_quantum_efficiency(32, temperature), // fp32
-        };
-        
-        // Riemann Zeta function for batch size optimization
-        let zeta_optimization = self.riemann_zeta_batch_optimization(b);
-        
-        // Fourier transform analysis of energy patterns
-        let fourier_energy = self.fourier_energy_transform(b, precision);
-        
-        // Mandelbrot set for computational complexity modeling
-        let complexity_factor = self.mandelbrot_computational_complexity(b);
-        
-        // Hyperbolic geometry for memory access patterns
-        let memory_geometry = self.hyperbolic_memory_geometry(b);
-        
-        // Quantum entanglement efficiency
-        let entanglement_factor = self.quantum_entanglement_efficiency(b, temperature);
-        
-        // Chaos theory optimization
-        let chaos_factor = self.chaos_theory_optimization(b);
-        
-        // Fractal dimension scaling
-        let fractal_scaling = self.fractal_dimension_scaling(b);
-        
-        // Topological invariants (Euler characteristic)
-        let topology_factor = self.topology_invariant_factor(b);
-        
-        // Advanced energy formula combining all mathematical concepts
-        let base_energy = self.model_params.base_power_consumption;
-        let quantum_energy = precision_quantum_factor * zeta_optimization * fourier_energy;
-        let geometric_energy = complexity_factor * memory_geometry * entanglement_factor;
-        let dynamic_energy = chaos_factor * fractal_scaling * topology_factor;
-        
-        // Information theory: Shannon entropy and Landauer's principle
-        let entropy_factor = -(b.ln() / b.ln().max(1.0)) * BOLTZMANN_CONSTANT * temperature;
-        let landauer_energy = BOLTZMANN_CONSTANT * temperature * (b.ln() / (2.0_f64).ln());
-        
-        // Thermodynamic efficiency (Carnot efficiency)
-        let carnot_efficiency = 1.0 - (temperature / (temperature + 100.0));
-        
-        // Final energy calculation
-        (base_energy + quantum_energy + geometric_energy + dynamic_energy + landauer_energy) 
-            * (1.0 + entropy_factor) * carnot_efficiency
-    }
-    
-    fn fibonacci_quantum_efficiency(&self, bits: u32, temperature: f64) -> f64 {
-        let fib_n = (bits as f64 * GOLDEN_RATIO).floor() as u32;
-        let quantum_tunneling = (-PLANCK_CONSTANT * bits as f64 / (BOLTZMANN_CONSTANT * temperature)).exp();
-        let efficiency = (fib_n as f64).sqrt() * quantum_tunneling;
-        efficiency * (1.0 + (temperature / 300.0).sin())
-    }
-    
-    fn riemann_zeta_batch_optimization(&self, batch_size: f64) -> f64 {
-        let s = 2.0 + (batch_size / 100.0).sin().abs();
-        let mut zeta_sum = 0.0;
-        for n in 1..=1000 {
-            zeta_sum += 1.0 / (n as f64).powf(s);
-        }
-        zeta_sum * (1.0 + (batch_size / 50.0).cos())
-    }
-    
-    fn fourier_energy_transform(&self, batch_size: f64, precision: &str) -> f64 {
-        let precision_harmonics: f64 = match precision {
-            "fp16" => 16.0,
-            "int8" => 8.0,
-            "int4" => 4.0,
-            _ => 32.0,
-        };
-        
-        let omega = 2.0 * PI * batch_size / 128.0;
-        let real_component = (omega).cos() * precision_harmonics.sqrt();
-        let imaginary_component = (omega).sin() * (precision_harmonics / 2.0).sqrt();
-        
-        (real_component.powi(2) + imaginary_component.powi(2)).sqrt()
-    }
-    
-    fn mandelbrot_computational_complexity(&self, batch_size: f64) -> f64 {
-        let c_real = (batch_size / 100.0) - 2.0;
-        let c_imag = (batch_size / 200.0) - 1.0;
-        
-        let mut z_real: f64 = 0.0;
-        let mut z_imag: f64 = 0.0;
-        let mut iterations = 0;
-        
-        while iterations < 100 && (z_real.powi(2) + z_imag.powi(2)) < 4.0 {
-            let temp = z_real.powi(2) - z_imag.powi(2) + c_real;
-            z_imag = 2.0 * z_real * z_imag + c_imag;
-            z_real = temp;
-            iterations += 1;
-        }
-        
-        1.0 + (iterations as f64 / 100.0)
-    }
-    
-    fn hyperbolic_memory_geometry(&self, batch_size: f64) -> f64 {
-        let hyperbolic_distance = (batch_size / 10.0).sinh();
-        let curvature = -1.0; // Negative curvature for hyperbolic space
-        let geodesic_factor = (curvature * hyperbolic_distance).cosh();
-        
-        1.0 + geodesic_factor / (1.0 + batch_size / 50.0)
-    }
-    
-    fn quantum_entanglement_efficiency(&self, batch_size: f64, temperature: f64) -> f64 {
-        let entanglement_entropy = -(batch_size / 64.0).ln() * (batch_size / 64.0);
-        let bell_state_fidelity = (PI * batch_size / 128.0).cos().abs();
-        let thermal_decoherence = (-temperature / 1000.0).exp();
-        
-        (1.0 + entanglement_entropy) * bell_state_fidelity * thermal_decoherence
-    }
-    
-    fn chaos_theory_optimization(&self, batch_size: f64) -> f64 {
-        let lyapunov_exponent = (batch_size / 32.0).sin() * 0.1;
-        let strange_attractor = (batch_size / 16.0).cos() * (batch_size / 24.0).sin();
-        
-        1.0 + lyapunov_exponent.abs() + strange_attractor.abs()
-    }
-    
-    fn fractal_dimension_scaling(&self, batch_size: f64) -> f64 {
-        let hausdorff_dimension = 1.0 + (batch_size.ln() / (batch_size + 1.0).ln());
-        let box_counting_dimension = (batch_size / 8.0).ln() / (2.0_f64).ln();
-        
-        hausdorff_dimension * (1.0 + box_counting_dimension / 10.0)
-    }
-    
-    fn topology_invariant_factor(&self, batch_size: f64) -> f64 {
-        let euler_characteristic = if batch_size as u32 % 2 == 0 { 2.0 } else { 0.0 };
-        let betti_numbers = (batch_size / 16.0).floor();
-        let genus = (batch_size / 32.0).floor();
-        
-        1.0 + (euler_characteristic + betti_numbers - 2.0 * genus) / 100.0
-    }
-}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use std::hint::black_box;
+
+use ai_energy::{ModelParams, Optimizer, Precision};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// Cost of the core energy model, alone and inside a batch-size sweep
+fn energy_benchmarks(c: &mut Criterion) {
+    let optimizer = Optimizer::new(ModelParams::builder().build().unwrap()).unwrap();
+    
+    c.bench_function("energy_consumption", |b| {
+        b.iter(|| optimizer.energy_consumption(black_box(32), black_box(Precision::Fp16)))
+    });
+    
+    c.bench_function("optimize_batch_size 1..=10000", |b| {
+        b.iter(|| optimizer.optimize_batch_size(black_box(Precision::Fp16), 1, black_box(10_000)))
+    });
+    
+    c.bench_function("energy_consumption loop 1..=1024", |b| {
+        b.iter(|| {
+            (1..=black_box(1024))
+                .map(|batch_size| optimizer.energy_consumption(batch_size, black_box(Precision::Fp16)).unwrap())
+                .collect::<Vec<f64>>()
+        })
+    });
+    
+    c.bench_function("energy_range 1..=1024", |b| {
+        b.iter(|| optimizer.energy_range(black_box(Precision::Fp16), 1, black_box(1024)))
+    });
+    
+    c.bench_function("advanced_energy_formula", |b| {
+        b.iter(|| optimizer.advanced_energy_formula(black_box(32), black_box(Precision::Fp16), black_box(300.0)))
+    });
+}
+
+criterion_group!(benches, energy_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use ai_energy::{ModelParams, Optimizer, ThermalModel};
+
+// Main function to demonstrate usage
+fn main() -> Result<(), Box<dyn Error>> {
+    // Example parameters for a neural network model
+    let model_params = ModelParams {
+        base_power_consumption: 50.0,  // watts
+        computation_factor: 2.5,      // computation power scaling factor
+        memory_usage: 4.0,            // GB
+        memory_power_factor: 5.0,     // watts per GB
+        inference_time: 0.05,         // seconds per inference at batch size 1
+        thermal_design_power: 100.0,  // Maximum thermal design power (watts)
+        cache_size: 32.0,             // Cache size in MB
+        memory_bandwidth: 256.0,      // Memory bandwidth in GB/s
+        startup_energy: 0.0,          // One-time warmup energy (joules)
+        sparsity: 0.0,                // Fraction of pruned weights (0 = dense)
+        accuracy_delta: BTreeMap::new(), // Accuracy drop per precision (none known)
+        thermal: ThermalModel::default(), // Throttling onset and ceiling
+        cold_start_penalty: 1.0,      // First-batch cold-cache slowdown (1.0 = none)
+        training_compute_multiplier: 3.0, // Training step compute vs. a forward pass
+        ops_per_inference: 8.2e9,     // Operations per sample (ResNet-50)
+        dvfs_points: Vec::new(),      // Selectable DVFS operating points (none = fixed clock)
+    };
+    
+    let optimizer = Optimizer::new(model_params)?;
+    
+    // Export data for Python visualization
+    optimizer.export_data(1, 128, "ai_energy_data.csv")?;
+    println!("Data exported to ai_energy_data.csv");
+    
+    Ok(())
+}
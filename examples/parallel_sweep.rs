@@ -0,0 +1,26 @@
+use std::error::Error;
+use std::time::Instant;
+
+use ai_energy::{ModelParams, Optimizer, Precision};
+
+// Compare the serial and rayon batch-size sweeps over a large range
+fn main() -> Result<(), Box<dyn Error>> {
+    let optimizer = Optimizer::new(ModelParams::builder().build()?)?;
+    let max_batch = 100_000;
+    
+    for precision in [Precision::Fp32, Precision::Fp16, Precision::Int8, Precision::Int4] {
+        let start = Instant::now();
+        let serial = optimizer.optimize_batch_size(precision, 1, max_batch)?;
+        let serial_time = start.elapsed();
+        
+        let start = Instant::now();
+        let parallel = optimizer.optimize_batch_size_parallel(precision, 1, max_batch)?;
+        let parallel_time = start.elapsed();
+        
+        assert_eq!(serial, parallel, "parallel sweep disagrees with serial scan");
+        println!("{}: batch {} ({:.4} samples/J), serial {:?}, parallel {:?}",
+            precision.as_str(), serial.0, serial.1, serial_time, parallel_time);
+    }
+    
+    Ok(())
+}
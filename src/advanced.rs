@@ -0,0 +1,415 @@
+// Advanced mathematical framework for AI energy modeling
+use core::f64::consts::PI;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::FloatMath;
+use crate::fit::FitError;
+use crate::model::Precision;
+use crate::optimizer::{check_scan_range, Optimizer, OptimizerError};
+use crate::units::BatchSize;
+
+// Mathematical constants
+const PLANCK_CONSTANT: f64 = 6.62607015e-34;
+const BOLTZMANN_CONSTANT: f64 = 1.380649e-23;
+const GOLDEN_RATIO: f64 = 1.618033988749;
+
+// cosh(sinh(b / 10)) overflows to infinity from batch 73, so the geodesic distance is
+// capped here (reached near batch 53); beyond it the geometry term stays finite
+const MAX_GEODESIC_DISTANCE: f64 = 100.0;
+
+// Zeta exponents are rounded to 1/ZETA_CACHE_RESOLUTION before summing, so repeated
+// sweeps reuse the same partial sums and results don't depend on call order
+const ZETA_CACHE_RESOLUTION: f64 = 10_000.0;
+#[cfg(feature = "std")]
+static ZETA_CACHE: OnceLock<Mutex<HashMap<u64, f64>>> = OnceLock::new();
+
+// Partial sum of the Riemann zeta series (1000 terms) at the rounded exponent
+#[cfg(feature = "std")]
+fn zeta_partial_sum(s: f64) -> f64 {
+    let key = (s * ZETA_CACHE_RESOLUTION).round() as u64;
+    let cache = ZETA_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut cache = cache.lock().unwrap_or_else(PoisonError::into_inner);
+    
+    *cache.entry(key).or_insert_with(|| zeta_series(key))
+}
+
+// Without std there is no global lock to share a cache, so sum on every call
+#[cfg(not(feature = "std"))]
+fn zeta_partial_sum(s: f64) -> f64 {
+    zeta_series((s * ZETA_CACHE_RESOLUTION).round() as u64)
+}
+
+fn zeta_series(key: u64) -> f64 {
+    let s = key as f64 / ZETA_CACHE_RESOLUTION;
+    (1..=1000).map(|n| 1.0 / (n as f64).powf(s)).sum()
+}
+
+// Landauer term of advanced_energy_formula: k*T*log2(batch_size) joules
+fn landauer_energy(batch_size: f64, temperature: f64) -> f64 {
+    BOLTZMANN_CONSTANT * temperature * (batch_size.ln() / (2.0_f64).ln())
+}
+
+// Components of advanced_energy_formula; total() recombines them into its result
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdvancedBreakdown {
+    pub base_energy: f64,       // base_power_consumption
+    pub quantum_energy: f64,    // Fibonacci precision factor * zeta * Fourier terms
+    pub geometric_energy: f64,  // Mandelbrot * hyperbolic * entanglement terms
+    pub dynamic_energy: f64,    // chaos * fractal * topology terms
+    pub landauer_energy: f64,   // k*T*log2(batch_size)
+    pub entropy_factor: f64,    // scales the sum by (1 + entropy_factor)
+    pub carnot_efficiency: f64, // scales the sum by this
+    pub calibration_scale: f64, // fitted by calibrate_advanced; 1.0 until then
+}
+
+impl AdvancedBreakdown {
+    // Final energy calculation
+    pub fn total(&self) -> f64 {
+        (self.base_energy + self.quantum_energy + self.geometric_energy + self.dynamic_energy + self.landauer_energy) 
+            * (1.0 + self.entropy_factor) * self.carnot_efficiency * self.calibration_scale
+    }
+}
+
+impl Optimizer {
+    // Advanced energy computation using mathematical principles. Finite and
+    // non-negative for every batch size >= 1 at positive temperatures (kelvin);
+    // batch size 0 is ZeroBatchSize.
+    pub fn advanced_energy_formula(&self, batch_size: u32, precision: Precision, temperature: f64) -> Result<f64, OptimizerError> {
+        Ok(self.advanced_breakdown(batch_size, precision, temperature)?.total())
+    }
+    
+    // The terms advanced_energy_formula combines, to see which one dominates
+    pub fn advanced_breakdown(&self, batch_size: u32, precision: Precision, temperature: f64) -> Result<AdvancedBreakdown, OptimizerError> {
+        let b = BatchSize::try_from(batch_size)?.get() as f64;
+        
+        // Fibonacci-based precision modeling with quantum field theory
+        let precision_quantum_factor = match precision {
+            Precision::Fp16 => self.fibonacci_quantum_efficiency(16, temperature),
+            Precision::Int8 => self.fibonacci_quantum_efficiency(8, temperature),
+            Precision::Int4 => self.fibonacci_quantum_efficiency(4, temperature),
+            Precision::Bf16 => self.fibonacci_quantum_efficiency(16, temperature),
+            Precision::Fp8 => self.fibonacci_quantum_efficiency(8, temperature),
+            Precision::Fp64 => self.fibonacci_quantum_efficiency(64, temperature),
+            Precision::Fp32 => self.fibonacci_quantum_efficiency(32, temperature),
+        };
+        
+        // Riemann Zeta function for batch size optimization
+        let zeta_optimization = self.riemann_zeta_batch_optimization(b);
+        
+        // Fourier transform analysis of energy patterns
+        let fourier_energy = self.fourier_energy_transform(b, precision);
+        
+        // Mandelbrot set for computational complexity modeling
+        let complexity_factor = self.mandelbrot_computational_complexity(b);
+        
+        // Hyperbolic geometry for memory access patterns
+        let memory_geometry = self.hyperbolic_memory_geometry(b);
+        
+        // Quantum entanglement efficiency
+        let entanglement_factor = self.quantum_entanglement_efficiency(b, temperature);
+        
+        // Chaos theory optimization
+        let chaos_factor = self.chaos_theory_optimization(b);
+        
+        // Fractal dimension scaling
+        let fractal_scaling = self.fractal_dimension_scaling(b);
+        
+        // Topological invariants (Euler characteristic)
+        let topology_factor = self.topology_invariant_factor(b);
+        
+        // Advanced energy formula combining all mathematical concepts
+        let base_energy = self.model_params.base_power_consumption;
+        let quantum_energy = precision_quantum_factor * zeta_optimization * fourier_energy;
+        let geometric_energy = complexity_factor * memory_geometry * entanglement_factor;
+        let dynamic_energy = chaos_factor * fractal_scaling * topology_factor;
+        
+        // Information theory: Shannon entropy and Landauer's principle
+        let entropy_factor = -(b.ln() / b.ln().max(1.0)) * BOLTZMANN_CONSTANT * temperature;
+        let landauer_energy = landauer_energy(b, temperature);
+        
+        // Thermodynamic efficiency (Carnot efficiency)
+        let carnot_efficiency = 1.0 - (temperature / (temperature + 100.0));
+        
+        Ok(AdvancedBreakdown {
+            base_energy,
+            quantum_energy,
+            geometric_energy,
+            dynamic_energy,
+            landauer_energy,
+            entropy_factor,
+            carnot_efficiency,
+            calibration_scale: self.calibration_scale,
+        })
+    }
+    
+    // Thermodynamic lower bound (joules) at a temperature in kelvin: k*T*log2(batch_size),
+    // the Landauer term advanced_energy_formula adds. Zero at batch size 1. Temperatures
+    // that are not finite and above 0 K return InvalidTemperature.
+    pub fn landauer_lower_bound(&self, batch_size: u32, temperature: f64) -> Result<f64, OptimizerError> {
+        let batch_size = BatchSize::try_from(batch_size)?;
+        if !(temperature > 0.0 && temperature.is_finite()) {
+            return Err(OptimizerError::InvalidTemperature);
+        }
+        Ok(landauer_energy(batch_size.get() as f64, temperature))
+    }
+    
+    // How many times the Landauer limit the modeled batch energy is; infinite at
+    // batch size 1, where the limit is zero
+    pub fn efficiency_vs_limit(&self, batch_size: u32, precision: Precision, temperature: f64) -> Result<f64, OptimizerError> {
+        let limit = self.landauer_lower_bound(batch_size, temperature)?;
+        Ok(self.energy_consumption(batch_size, precision)? / limit)
+    }
+    
+    // Fit calibration_scale by least squares so advanced_energy_formula matches known
+    // (batch_size, temperature, joules) energies at FP32, e.g. energy_consumption at
+    // those batches or FP32 measurements, bringing it onto real joules. The scale then
+    // applies at every precision. Returns the fitted scale.
+    pub fn calibrate_advanced(&mut self, known_points: &[(u32, f64, f64)]) -> Result<f64, FitError> {
+        if known_points.is_empty() {
+            return Err(FitError::NoMeasurements);
+        }
+        if let Some(index) = known_points.iter().position(|&(batch_size, _, energy)| batch_size == 0 || energy.is_nan() || energy <= 0.0) {
+            return Err(FitError::InvalidMeasurement(index));
+        }
+        
+        // Minimize sum (s * a_i - e_i)^2 over the scale s, with a_i the uncalibrated
+        // formula: s = sum(a_i * e_i) / sum(a_i^2)
+        let mut uncalibrated = self.clone();
+        uncalibrated.calibration_scale = 1.0;
+        let (mut cross, mut squares) = (0.0, 0.0);
+        for (index, &(batch_size, temperature, energy)) in known_points.iter().enumerate() {
+            let advanced = uncalibrated
+                .advanced_energy_formula(batch_size, Precision::Fp32, temperature)
+                .map_err(|_| FitError::InvalidMeasurement(index))?;
+            cross += advanced * energy;
+            squares += advanced * advanced;
+        }
+        if !(squares > 0.0 && squares.is_finite()) {
+            return Err(FitError::Degenerate);
+        }
+        
+        self.calibration_scale = cross / squares;
+        Ok(self.calibration_scale)
+    }
+    
+    pub fn calibration_scale(&self) -> f64 {
+        self.calibration_scale
+    }
+    
+    // Ratio of advanced_energy_formula to energy_consumption at one operating point.
+    // The two models are not on a common scale, so this is far from 1 without
+    // calibration.
+    pub fn model_divergence(&self, batch_size: u32, precision: Precision, temperature: f64) -> Result<f64, OptimizerError> {
+        let basic = self.energy_consumption(batch_size, precision)?;
+        Ok(self.advanced_energy_formula(batch_size, precision, temperature)? / basic)
+    }
+    
+    // Batch size in the range where the two models disagree most, in either direction
+    // (largest |ln ratio|), with its model_divergence. Ties go to the smaller batch.
+    pub fn max_model_divergence(&self, precision: Precision, min_batch: u32, max_batch: u32, temperature: f64) -> Result<(u32, f64), OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        let mut worst = (min_batch, self.model_divergence(min_batch, precision, temperature)?);
+        
+        for batch_size in min_batch.saturating_add(1)..=max_batch {
+            let divergence = self.model_divergence(batch_size, precision, temperature)?;
+            if divergence.ln().abs() > worst.1.ln().abs() {
+                worst = (batch_size, divergence);
+            }
+        }
+        
+        Ok(worst)
+    }
+    
+    pub(crate) fn fibonacci_quantum_efficiency(&self, bits: u32, temperature: f64) -> f64 {
+        let fib_n = (bits as f64 * GOLDEN_RATIO).floor() as u32;
+        let quantum_tunneling = (-PLANCK_CONSTANT * bits as f64 / (BOLTZMANN_CONSTANT * temperature)).exp();
+        let efficiency = (fib_n as f64).sqrt() * quantum_tunneling;
+        efficiency * (1.0 + (temperature / 300.0).sin())
+    }
+    
+    pub(crate) fn riemann_zeta_batch_optimization(&self, batch_size: f64) -> f64 {
+        let s = 2.0 + (batch_size / 100.0).sin().abs();
+        zeta_partial_sum(s) * (1.0 + (batch_size / 50.0).cos())
+    }
+    
+    pub(crate) fn fourier_energy_transform(&self, batch_size: f64, precision: Precision) -> f64 {
+        let precision_harmonics = self.precision_profile.get(precision).harmonics;
+        
+        let omega = 2.0 * PI * batch_size / 128.0;
+        let real_component = (omega).cos() * precision_harmonics.sqrt();
+        let imaginary_component = (omega).sin() * (precision_harmonics / 2.0).sqrt();
+        
+        (real_component.powi(2) + imaginary_component.powi(2)).sqrt()
+    }
+    
+    pub(crate) fn mandelbrot_computational_complexity(&self, batch_size: f64) -> f64 {
+        let c_real = (batch_size / 100.0) - 2.0;
+        let c_imag = (batch_size / 200.0) - 1.0;
+        
+        let mut z_real: f64 = 0.0;
+        let mut z_imag: f64 = 0.0;
+        let mut iterations = 0;
+        
+        while iterations < 100 && (z_real.powi(2) + z_imag.powi(2)) < 4.0 {
+            let temp = z_real.powi(2) - z_imag.powi(2) + c_real;
+            z_imag = 2.0 * z_real * z_imag + c_imag;
+            z_real = temp;
+            iterations += 1;
+        }
+        
+        1.0 + (iterations as f64 / 100.0)
+    }
+    
+    pub(crate) fn hyperbolic_memory_geometry(&self, batch_size: f64) -> f64 {
+        let hyperbolic_distance = (batch_size / 10.0).sinh().min(MAX_GEODESIC_DISTANCE);
+        let curvature = -1.0; // Negative curvature for hyperbolic space
+        let geodesic_factor = (curvature * hyperbolic_distance).cosh();
+        
+        1.0 + geodesic_factor / (1.0 + batch_size / 50.0)
+    }
+    
+    pub(crate) fn quantum_entanglement_efficiency(&self, batch_size: f64, temperature: f64) -> f64 {
+        let entanglement_entropy = -(batch_size / 64.0).ln() * (batch_size / 64.0);
+        let bell_state_fidelity = (PI * batch_size / 128.0).cos().abs();
+        let thermal_decoherence = (-temperature / 1000.0).exp();
+        
+        // -x ln x drops below -1 past batch 112; floor at zero rather than go negative
+        (1.0 + entanglement_entropy).max(0.0) * bell_state_fidelity * thermal_decoherence
+    }
+    
+    pub(crate) fn chaos_theory_optimization(&self, batch_size: f64) -> f64 {
+        let lyapunov_exponent = (batch_size / 32.0).sin() * 0.1;
+        let strange_attractor = (batch_size / 16.0).cos() * (batch_size / 24.0).sin();
+        
+        1.0 + lyapunov_exponent.abs() + strange_attractor.abs()
+    }
+    
+    pub(crate) fn fractal_dimension_scaling(&self, batch_size: f64) -> f64 {
+        let hausdorff_dimension = 1.0 + (batch_size.ln() / (batch_size + 1.0).ln());
+        let box_counting_dimension = (batch_size / 8.0).ln() / (2.0_f64).ln();
+        
+        hausdorff_dimension * (1.0 + box_counting_dimension / 10.0)
+    }
+    
+    pub(crate) fn topology_invariant_factor(&self, batch_size: f64) -> f64 {
+        let euler_characteristic = if (batch_size as u32).is_multiple_of(2) { 2.0 } else { 0.0 };
+        let betti_numbers = (batch_size / 16.0).floor();
+        let genus = (batch_size / 32.0).floor();
+        
+        1.0 + (euler_characteristic + betti_numbers - 2.0 * genus) / 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ModelParams;
+    use alloc::vec::Vec;
+    
+    type BatchHelper = fn(&Optimizer, f64) -> f64;
+    
+    fn optimizer() -> Optimizer {
+        Optimizer::new(ModelParams::default()).unwrap()
+    }
+    
+    fn assert_pinned(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() <= 1e-12 * expected.abs().max(1.0), "{} != {}", actual, expected);
+    }
+    
+    #[test]
+    fn helper_outputs_are_pinned() {
+        let optimizer = optimizer();
+        assert_pinned(optimizer.fibonacci_quantum_efficiency(8, 300.0), 6.379042612694182);
+        assert_pinned(optimizer.fibonacci_quantum_efficiency(32, 300.0), 13.15073324117659);
+        
+        let pins: [(BatchHelper, [f64; 2]); 7] = [
+            (Optimizer::riemann_zeta_batch_optimization, [3.2691400852074097, 2.567359093747564]),
+            (Optimizer::mandelbrot_computational_complexity, [1.01, 1.02]),
+            (Optimizer::hyperbolic_memory_geometry, [1.9853145929762657, 63453.100296382865]),
+            (|optimizer, b| optimizer.quantum_entanglement_efficiency(b, 300.0), [0.7887208568553713, 0.7053858608838783]),
+            (Optimizer::chaos_theory_optimization, [1.044697772601529, 1.4886159814534008]),
+            (Optimizer::fractal_dimension_scaling, [0.7, 2.389439179023363]),
+            (Optimizer::topology_invariant_factor, [1.0, 1.02]),
+        ];
+        for (helper, expected) in pins {
+            assert_pinned(helper(&optimizer, 1.0), expected[0]);
+            assert_pinned(helper(&optimizer, 32.0), expected[1]);
+        }
+        assert_pinned(optimizer.fourier_energy_transform(1.0, Precision::Fp16), 3.997591638310345);
+        assert_pinned(optimizer.fourier_energy_transform(32.0, Precision::Fp16), 2.8284271247461903);
+    }
+    
+    #[test]
+    fn zeta_cache_returns_the_uncached_sum() {
+        for b in [1.0_f64, 17.0, 250.0] {
+            let s = 2.0 + (b / 100.0).sin().abs();
+            let key = (s * ZETA_CACHE_RESOLUTION).round() as u64;
+            assert_eq!(zeta_partial_sum(s), zeta_series(key));
+            assert_eq!(zeta_partial_sum(s), zeta_partial_sum(s));
+        }
+    }
+    
+    #[test]
+    fn formula_is_finite_and_non_negative_over_the_grid() {
+        let optimizer = optimizer();
+        for precision in Precision::ALL {
+            for temperature in 1..=1000 {
+                let temperature = temperature as f64;
+                for batch_size in 1..=1024 {
+                    let energy = optimizer.advanced_energy_formula(batch_size, precision, temperature).unwrap();
+                    assert!(energy.is_finite() && energy >= 0.0, "{:?} batch {} at {} K: {}", precision, batch_size, temperature, energy);
+                }
+            }
+        }
+        for precision in Precision::ALL {
+            assert_eq!(optimizer.advanced_energy_formula(0, precision, 300.0), Err(OptimizerError::ZeroBatchSize));
+        }
+    }
+    
+    #[test]
+    fn calibration_reduces_rmse_against_the_basic_model() {
+        let mut optimizer = optimizer();
+        let known: Vec<(u32, f64, f64)> = (1..=64)
+            .map(|batch_size| (batch_size, 300.0, optimizer.energy_consumption(batch_size, Precision::Fp32).unwrap()))
+            .collect();
+        let rmse = |optimizer: &Optimizer| {
+            let squares: f64 = known
+                .iter()
+                .map(|&(batch_size, temperature, energy)| {
+                    let advanced = optimizer.advanced_energy_formula(batch_size, Precision::Fp32, temperature).unwrap();
+                    (advanced - energy) * (advanced - energy)
+                })
+                .sum();
+            (squares / known.len() as f64).sqrt()
+        };
+        
+        let before = rmse(&optimizer);
+        let scale = optimizer.calibrate_advanced(&known).unwrap();
+        assert_eq!(optimizer.calibration_scale(), scale);
+        assert!(rmse(&optimizer) < 0.5 * before, "{} -> {}", before, rmse(&optimizer));
+        
+        assert_eq!(optimizer.calibrate_advanced(&[]), Err(FitError::NoMeasurements));
+        assert_eq!(optimizer.calibrate_advanced(&[(4, 300.0, 1.0), (0, 300.0, 1.0)]), Err(FitError::InvalidMeasurement(1)));
+    }
+    
+    #[test]
+    fn landauer_bound_is_k_t_log2_batch() {
+        let optimizer = optimizer();
+        let bound = optimizer.landauer_lower_bound(8, 300.0).unwrap();
+        let expected = 1.380649e-23 * 300.0 * 3.0;
+        assert!((bound - expected).abs() <= 1e-12 * expected, "{} != {}", bound, expected);
+        
+        assert_eq!(optimizer.landauer_lower_bound(1, 300.0), Ok(0.0));
+        assert_eq!(optimizer.efficiency_vs_limit(1, Precision::Fp32, 300.0), Ok(f64::INFINITY));
+        assert!(optimizer.efficiency_vs_limit(8, Precision::Fp32, 300.0).unwrap() > 1e15);
+        
+        for temperature in [0.0, -10.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(optimizer.landauer_lower_bound(8, temperature), Err(OptimizerError::InvalidTemperature));
+            assert_eq!(optimizer.efficiency_vs_limit(8, Precision::Fp32, temperature), Err(OptimizerError::InvalidTemperature));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// Command-line front end: `aie [model flags | --profile file.json] <optimize|energy> ...`
+use std::error::Error;
+use std::process::ExitCode;
+
+use ai_energy::{ModelParams, Optimizer, Precision};
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(name = "aie", about = "Energy efficiency optimizer for AI model inference")]
+struct Cli {
+    #[command(flatten)]
+    model: ModelArgs,
+    
+    #[command(subcommand)]
+    command: Command,
+}
+
+// Model parameters; flags override the profile, which overrides the builder defaults
+#[derive(Args)]
+struct ModelArgs {
+    /// Load ModelParams from a JSON profile
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Base power consumption (watts)
+    #[arg(long, global = true)]
+    base_power: Option<f64>,
+    /// Computation power scaling factor
+    #[arg(long, global = true)]
+    compute_factor: Option<f64>,
+    /// Memory usage (GB)
+    #[arg(long, global = true)]
+    memory_usage: Option<f64>,
+    /// Memory power (watts per GB)
+    #[arg(long, global = true)]
+    memory_power_factor: Option<f64>,
+    /// Seconds per inference at batch size 1
+    #[arg(long, global = true)]
+    inference_time: Option<f64>,
+    /// Thermal design power (watts)
+    #[arg(long, global = true)]
+    tdp: Option<f64>,
+    /// Cache size (MB)
+    #[arg(long, global = true)]
+    cache_size: Option<f64>,
+    /// Memory bandwidth (GB/s)
+    #[arg(long, global = true)]
+    memory_bandwidth: Option<f64>,
+    /// One-time warmup energy (joules)
+    #[arg(long, global = true)]
+    startup_energy: Option<f64>,
+    /// Fraction of weights pruned to zero, in [0, 1)
+    #[arg(long, global = true)]
+    sparsity: Option<f64>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the most energy-efficient batch size in a range
+    Optimize {
+        #[arg(long, default_value = "fp32")]
+        precision: Precision,
+        #[arg(long, default_value_t = 1)]
+        min: u32,
+        #[arg(long, default_value_t = 128)]
+        max: u32,
+    },
+    /// Print the energy (joules) of one batch
+    Energy {
+        #[arg(long)]
+        batch: u32,
+        #[arg(long, default_value = "fp32")]
+        precision: Precision,
+    },
+}
+
+impl ModelArgs {
+    fn params(&self) -> Result<ModelParams, Box<dyn Error>> {
+        let mut params = match &self.profile {
+            Some(path) => ModelParams::from_json_file(path)?,
+            None => ModelParams::builder().build()?,
+        };
+        
+        let overrides = [
+            (self.base_power, &mut params.base_power_consumption),
+            (self.compute_factor, &mut params.computation_factor),
+            (self.memory_usage, &mut params.memory_usage),
+            (self.memory_power_factor, &mut params.memory_power_factor),
+            (self.inference_time, &mut params.inference_time),
+            (self.tdp, &mut params.thermal_design_power),
+            (self.cache_size, &mut params.cache_size),
+            (self.memory_bandwidth, &mut params.memory_bandwidth),
+            (self.startup_energy, &mut params.startup_energy),
+            (self.sparsity, &mut params.sparsity),
+        ];
+        for (value, field) in overrides {
+            if let Some(value) = value {
+                *field = value;
+            }
+        }
+        
+        Ok(params)
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let optimizer = Optimizer::new(cli.model.params()?)?;
+    
+    match cli.command {
+        Command::Optimize { precision, min, max } => {
+            let (batch, efficiency) = optimizer.optimize_batch_size(precision, min, max)?;
+            println!("{} ({} samples/J)", batch, efficiency);
+        }
+        Command::Energy { batch, precision } => {
+            println!("{}", optimizer.energy_consumption(batch, precision)?);
+        }
+    }
+    
+    Ok(())
+}
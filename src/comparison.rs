@@ -0,0 +1,62 @@
+// Side-by-side efficiency comparison of several candidate models
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::model::{ModelParams, Precision, ValidationError};
+use crate::optimizer::{Optimizer, OptimizerError};
+
+// A set of labelled models evaluated at the same operating point
+#[derive(Debug, Clone)]
+pub struct ModelComparison {
+    models: Vec<(String, Optimizer)>,
+}
+
+impl ModelComparison {
+    // Build optimizers for every labelled profile, rejecting invalid parameters
+    pub fn new(models: Vec<(String, ModelParams)>) -> Result<Self, ValidationError> {
+        let models = models
+            .into_iter()
+            .map(|(label, params)| Ok((label, Optimizer::new(params)?)))
+            .collect::<Result<_, ValidationError>>()?;
+        Ok(ModelComparison { models })
+    }
+    
+    // Label and efficiency of the most efficient model, or None if there are no models
+    pub fn best_model_at(&self, batch_size: u32, precision: Precision) -> Result<Option<(&str, f64)>, OptimizerError> {
+        Ok(self.rank_models(batch_size, precision)?.into_iter().next())
+    }
+    
+    // All models with their efficiency, most efficient first
+    pub fn rank_models(&self, batch_size: u32, precision: Precision) -> Result<Vec<(&str, f64)>, OptimizerError> {
+        let mut ranking = self
+            .models
+            .iter()
+            .map(|(label, optimizer)| Ok((label.as_str(), optimizer.efficiency(batch_size, precision)?)))
+            .collect::<Result<Vec<_>, OptimizerError>>()?;
+        ranking.sort_by(|a, b| b.1.total_cmp(&a.1));
+        Ok(ranking)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use alloc::vec;
+    
+    #[test]
+    fn models_rank_by_efficiency_at_the_batch_size() {
+        let lean = ModelParams::builder().base_power_consumption(20.0).build().unwrap();
+        let heavy = ModelParams::builder().base_power_consumption(90.0).build().unwrap();
+        let comparison = ModelComparison::new(vec![("heavy".to_string(), heavy), ("lean".to_string(), lean)]).unwrap();
+        
+        let (best, best_efficiency) = comparison.best_model_at(16, Precision::Fp16).unwrap().unwrap();
+        assert_eq!(best, "lean");
+        let ranking = comparison.rank_models(16, Precision::Fp16).unwrap();
+        assert_eq!(ranking.iter().map(|&(label, _)| label).collect::<Vec<_>>(), ["lean", "heavy"]);
+        assert_eq!(ranking[0].1, best_efficiency);
+        assert!(ranking[0].1 > ranking[1].1);
+        
+        assert_eq!(ModelComparison::new(Vec::new()).unwrap().best_model_at(16, Precision::Fp16), Ok(None));
+    }
+}
@@ -0,0 +1,182 @@
+// Least-squares calibration of ModelParams against measured batch energies
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::FloatMath;
+use crate::model::{ModelParams, Precision};
+use crate::optimizer::Optimizer;
+
+// Nelder-Mead settings: stop once the simplex's objective values agree to within
+// FIT_TOLERANCE or after FIT_MAX_ITERATIONS reflections
+const FIT_TOLERANCE: f64 = 1e-14;
+const FIT_MAX_ITERATIONS: usize = 5000;
+
+// Errors returned by ModelParams::fit and Optimizer::calibrate_advanced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FitError {
+    NoMeasurements,            // nothing to fit against
+    InvalidMeasurement(usize), // index of a measurement with batch 0 or energy not finite and > 0
+    Degenerate,                // the model gives no usable (non-zero, finite) values to fit
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FitError::NoMeasurements => write!(f, "at least one measurement is required"),
+            FitError::InvalidMeasurement(index) => {
+                write!(f, "measurement {} needs batch size >= 1 and a finite energy > 0", index)
+            }
+            FitError::Degenerate => write!(f, "the model is zero or not finite at the measurements, so no scale fits"),
+        }
+    }
+}
+
+impl Error for FitError {}
+
+impl ModelParams {
+    // Fit base_power_consumption, computation_factor, and memory_power_factor to
+    // (batch_size, precision, joules) measurements, keeping the defaults for
+    // every other field. Returns the fitted parameters and the largest relative error
+    // between energy_consumption and any measurement.
+    pub fn fit(measurements: &[(u32, Precision, f64)]) -> Result<(ModelParams, f64), FitError> {
+        ModelParams::default().fit_from(measurements)
+    }
+    
+    // Like fit, but starting from (and keeping the unfitted fields of) these parameters
+    pub fn fit_from(&self, measurements: &[(u32, Precision, f64)]) -> Result<(ModelParams, f64), FitError> {
+        if measurements.is_empty() {
+            return Err(FitError::NoMeasurements);
+        }
+        if let Some(index) = measurements.iter().position(|&(batch_size, _, energy)| batch_size == 0 || !energy.is_finite() || energy <= 0.0) {
+            return Err(FitError::InvalidMeasurement(index));
+        }
+        
+        // Search in log space so the fitted values stay positive
+        let with_point = |point: &[f64; 3]| ModelParams {
+            base_power_consumption: point[0].exp(),
+            computation_factor: point[1].exp(),
+            memory_power_factor: point[2].exp(),
+            ..self.clone()
+        };
+        let relative_errors = |params: ModelParams| -> Vec<f64> {
+            let optimizer = match Optimizer::new(params) {
+                Ok(optimizer) => optimizer,
+                Err(_) => return vec![f64::INFINITY],
+            };
+            measurements
+                .iter()
+                .map(|&(batch_size, precision, energy)| match optimizer.energy_consumption(batch_size, precision) {
+                    Ok(modeled) => (modeled - energy) / energy,
+                    Err(_) => f64::INFINITY,
+                })
+                .collect()
+        };
+        let objective = |point: &[f64; 3]| relative_errors(with_point(point)).iter().map(|error| error * error).sum();
+        
+        let start = [
+            self.base_power_consumption.max(1e-3).ln(),
+            self.computation_factor.max(1e-3).ln(),
+            self.memory_power_factor.max(1e-3).ln(),
+        ];
+        let best = nelder_mead(start, objective);
+        
+        let fitted = with_point(&best);
+        let max_error = relative_errors(fitted.clone()).iter().fold(0.0_f64, |max, error| max.max(error.abs()));
+        Ok((fitted, max_error))
+    }
+}
+
+// Minimize f from start with the Nelder-Mead simplex method (standard coefficients)
+fn nelder_mead(start: [f64; 3], f: impl Fn(&[f64; 3]) -> f64) -> [f64; 3] {
+    let mut simplex: Vec<([f64; 3], f64)> = (0..=3)
+        .map(|vertex| {
+            let mut point = start;
+            if vertex > 0 {
+                point[vertex - 1] += 0.5;
+            }
+            (point, f(&point))
+        })
+        .collect();
+    
+    let along = |from: &[f64; 3], to: &[f64; 3], t: f64| -> [f64; 3] {
+        [0, 1, 2].map(|i| from[i] + t * (to[i] - from[i]))
+    };
+    
+    for _ in 0..FIT_MAX_ITERATIONS {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        if simplex[3].1 - simplex[0].1 <= FIT_TOLERANCE {
+            break;
+        }
+        
+        let centroid = [0, 1, 2].map(|i| simplex[..3].iter().map(|(point, _)| point[i]).sum::<f64>() / 3.0);
+        let worst = simplex[3];
+        
+        let reflected = along(&centroid, &worst.0, -1.0);
+        let reflected_value = f(&reflected);
+        if reflected_value < simplex[0].1 {
+            let expanded = along(&centroid, &worst.0, -2.0);
+            let expanded_value = f(&expanded);
+            simplex[3] = if expanded_value < reflected_value { (expanded, expanded_value) } else { (reflected, reflected_value) };
+        } else if reflected_value < simplex[2].1 {
+            simplex[3] = (reflected, reflected_value);
+        } else {
+            let contracted = along(&centroid, &worst.0, 0.5);
+            let contracted_value = f(&contracted);
+            if contracted_value < worst.1 {
+                simplex[3] = (contracted, contracted_value);
+            } else {
+                // Shrink every vertex toward the best one
+                let best = simplex[0].0;
+                for vertex in simplex.iter_mut().skip(1) {
+                    vertex.0 = along(&best, &vertex.0, 0.5);
+                    vertex.1 = f(&vertex.0);
+                }
+            }
+        }
+    }
+    
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex[0].0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn measurements_from(params: ModelParams) -> Vec<(u32, Precision, f64)> {
+        let optimizer = Optimizer::new(params).unwrap();
+        let mut measurements = Vec::new();
+        for precision in [Precision::Fp32, Precision::Fp16, Precision::Int8] {
+            for batch_size in [1, 4, 16, 64] {
+                measurements.push((batch_size, precision, optimizer.energy_consumption(batch_size, precision).unwrap()));
+            }
+        }
+        measurements
+    }
+    
+    #[test]
+    fn fitted_params_reproduce_the_measurements() {
+        let truth = ModelParams::builder().base_power_consumption(80.0).computation_factor(2.5).memory_power_factor(3.0).build().unwrap();
+        let measurements = measurements_from(truth);
+        let (fitted, max_error) = ModelParams::fit(&measurements).unwrap();
+        assert!(max_error < 1e-4, "max relative error {}", max_error);
+        
+        let optimizer = Optimizer::new(fitted).unwrap();
+        for (batch_size, precision, energy) in measurements {
+            let modeled = optimizer.energy_consumption(batch_size, precision).unwrap();
+            assert!((modeled - energy).abs() <= max_error * energy, "batch {} {}: {} vs {}", batch_size, precision, modeled, energy);
+        }
+    }
+    
+    #[test]
+    fn unusable_measurements_are_rejected() {
+        assert_eq!(ModelParams::fit(&[]), Err(FitError::NoMeasurements));
+        for energy in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(ModelParams::fit(&[(8, Precision::Fp32, 1.0), (4, Precision::Fp16, energy)]), Err(FitError::InvalidMeasurement(1)));
+        }
+        assert_eq!(ModelParams::fit(&[(0, Precision::Fp32, 1.0)]), Err(FitError::InvalidMeasurement(0)));
+    }
+}
@@ -0,0 +1,48 @@
+// AI Energy Efficiency Optimizer
+//
+// `model` holds the hardware/model description, `optimizer` the core energy model
+// and batch-size search, `advanced` the mathematical energy formula, `comparison`
+// the multi-model helpers, `fit` calibration against measurements, `online`
+// incremental re-tuning as load changes, `reference` loading and comparison of
+// recorded energy curves, and `units` the typed `Energy` and `BatchSize` quantities.
+//
+// The default `std` feature can be turned off for embedded targets; build with
+// `--no-default-features --features libm` to get the energy model, batch-size
+// search, and Prometheus output without the file/writer exporters.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("ai_energy needs either the `std` feature or, for no_std builds, the `libm` feature");
+
+extern crate alloc;
+
+mod advanced;
+mod comparison;
+mod fit;
+// The test harness links std, whose inherent float methods then shadow FloatMath
+#[cfg(not(any(feature = "std", test)))]
+mod math;
+mod model;
+mod online;
+mod optimizer;
+#[cfg(feature = "python")]
+mod python;
+mod reference;
+mod units;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+pub use advanced::AdvancedBreakdown;
+pub use comparison::ModelComparison;
+pub use fit::FitError;
+#[cfg(feature = "serde")]
+pub use model::ProfileError;
+pub use model::{DvfsPoint, FieldMeta, HardwarePreset, LayerSpec, ModelParams, ModelParamsBuilder, ParseError, Precision, PrecisionFactors, PrecisionProfile, ThermalModel, ValidationError};
+pub use online::OnlineOptimizer;
+pub use optimizer::{break_even_volume, upgrade_breakeven_utilization, BatchComparison, Bound, EfficiencyStats, EnergyBreakdown, EstimateSummary, FleetPlan, LlmEnergyBreakdown, Optimizer, OptimizerError, PowerDraw, PrecisionRow, PrecisionTable, Preference, ProfileStats, SavingsReport, SensitivityReport, ServeStats};
+#[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+pub use reference::load_reference_csv;
+#[cfg(feature = "std")]
+pub use reference::{read_reference_csv, ReferenceError};
+pub use reference::{ComparisonReport, DatasetRow, EnergyDataset, PointComparison, ReferencePoint};
+pub use units::{BatchSize, Energy};
@@ -0,0 +1,31 @@
+// f64 math for no_std builds, where the inherent float methods are unavailable.
+// Each method forwards to libm under the same name as its std counterpart.
+pub(crate) trait FloatMath {
+    fn ceil(self) -> f64;
+    fn floor(self) -> f64;
+    fn round(self) -> f64;
+    fn sqrt(self) -> f64;
+    fn exp(self) -> f64;
+    fn ln(self) -> f64;
+    fn powf(self, n: f64) -> f64;
+    fn powi(self, n: i32) -> f64;
+    fn sin(self) -> f64;
+    fn cos(self) -> f64;
+    fn sinh(self) -> f64;
+    fn cosh(self) -> f64;
+}
+
+impl FloatMath for f64 {
+    fn ceil(self) -> f64 { libm::ceil(self) }
+    fn floor(self) -> f64 { libm::floor(self) }
+    fn round(self) -> f64 { libm::round(self) }
+    fn sqrt(self) -> f64 { libm::sqrt(self) }
+    fn exp(self) -> f64 { libm::exp(self) }
+    fn ln(self) -> f64 { libm::log(self) }
+    fn powf(self, n: f64) -> f64 { libm::pow(self, n) }
+    fn powi(self, n: i32) -> f64 { libm::pow(self, n as f64) }
+    fn sin(self) -> f64 { libm::sin(self) }
+    fn cos(self) -> f64 { libm::cos(self) }
+    fn sinh(self) -> f64 { libm::sinh(self) }
+    fn cosh(self) -> f64 { libm::cosh(self) }
+}
@@ -0,0 +1,732 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "serde")]
+use std::fs::File;
+#[cfg(feature = "serde")]
+use std::io::Write;
+
+// Structure to hold AI model parameters
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelParams {
+    pub base_power_consumption: f64, // watts
+    pub computation_factor: f64,     // computation power scaling factor
+    pub memory_usage: f64,           // GB
+    pub memory_power_factor: f64,    // watts per GB
+    pub inference_time: f64,         // seconds per inference at batch size 1
+    // New enhanced parameters
+    pub thermal_design_power: f64,   // Maximum thermal design power (watts)
+    pub cache_size: f64,             // Cache size in MB
+    pub memory_bandwidth: f64,       // Memory bandwidth in GB/s
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub startup_energy: f64,         // One-time load/warmup energy in joules (0 = steady state only)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sparsity: f64,               // Fraction of weights pruned to zero, in [0, 1) (0 = dense)
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub accuracy_delta: BTreeMap<Precision, f64>, // Accuracy drop (percentage points) per precision; missing = no drop
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub thermal: ThermalModel,       // Compute-power throttling curve under sustained load
+    #[cfg_attr(feature = "serde", serde(default = "no_cold_start_penalty"))]
+    pub cold_start_penalty: f64,     // Inference-time multiplier of the first, cold-cache batch (1.0 = none)
+    #[cfg_attr(feature = "serde", serde(default = "forward_backward_multiplier"))]
+    pub training_compute_multiplier: f64, // Compute of a training step relative to a forward pass
+    #[cfg_attr(feature = "serde", serde(default = "resnet50_ops"))]
+    pub ops_per_inference: f64,      // Arithmetic operations per sample, for TOPS/W reporting
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub dvfs_points: Vec<DvfsPoint>, // Selectable operating points; the fastest matches the base model (none = fixed clock)
+}
+
+#[cfg(feature = "serde")]
+fn no_cold_start_penalty() -> f64 {
+    ModelParams::default().cold_start_penalty
+}
+
+#[cfg(feature = "serde")]
+fn resnet50_ops() -> f64 {
+    ModelParams::default().ops_per_inference
+}
+
+#[cfg(feature = "serde")]
+fn forward_backward_multiplier() -> f64 {
+    ModelParams::default().training_compute_multiplier
+}
+
+// Compute-power penalty from heat at high load: 1 + min(batch / onset, max_penalty),
+// where onset is onset_batch scaled by thermal_design_power / 100 W, so a chip with
+// more thermal headroom throttles later. Defaults: onset_batch 64.0, max_penalty 0.25
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThermalModel {
+    pub onset_batch: f64, // batch size over which the penalty grows, at a 100 W TDP
+    pub max_penalty: f64, // largest fractional increase in compute power
+}
+
+// One dynamic voltage/frequency scaling operating point of the compute units
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DvfsPoint {
+    pub freq_ghz: f64, // core clock
+    pub voltage: f64,  // core supply voltage (volts)
+}
+
+impl Default for ThermalModel {
+    fn default() -> Self {
+        ModelParams::default().thermal
+    }
+}
+
+// The demo profile: every numeric field at its field_metadata() default (50 W base
+// power, 100 W TDP, 256 GB/s, ...), no accuracy_delta entries, and no DVFS points
+impl Default for ModelParams {
+    fn default() -> Self {
+        ModelParams::from_field_values(FIELD_METADATA.map(|meta| meta.default))
+    }
+}
+
+impl ModelParams {
+    // Start building parameters from the documented defaults
+    pub fn builder() -> ModelParamsBuilder {
+        ModelParamsBuilder::new()
+    }
+    
+    // Load a hardware profile from a JSON file
+    #[cfg(feature = "serde")]
+    pub fn from_json_file(filename: &str) -> Result<Self, ProfileError> {
+        let file = File::open(filename)?;
+        Ok(serde_json::from_reader(std::io::BufReader::new(file))?)
+    }
+    
+    // Save this hardware profile as pretty-printed JSON
+    #[cfg(feature = "serde")]
+    pub fn to_json_file(&self, filename: &str) -> Result<(), ProfileError> {
+        let mut file = File::create(filename)?;
+        serde_json::to_writer_pretty(&mut file, self)?;
+        writeln!(file)?;
+        Ok(())
+    }
+    
+    // Name, unit, default, and valid range of every numeric field, in declaration
+    // order (thermal fields as "thermal.<field>"); validate checks against this table
+    pub fn field_metadata() -> &'static [FieldMeta] {
+        &FIELD_METADATA
+    }
+    
+    // Current values of the FIELD_METADATA fields, in the same order
+    fn field_values(&self) -> [f64; FIELD_COUNT] {
+        [
+            self.base_power_consumption,
+            self.computation_factor,
+            self.memory_usage,
+            self.memory_power_factor,
+            self.inference_time,
+            self.thermal_design_power,
+            self.cache_size,
+            self.memory_bandwidth,
+            self.startup_energy,
+            self.sparsity,
+            self.thermal.onset_batch,
+            self.thermal.max_penalty,
+            self.cold_start_penalty,
+            self.training_compute_multiplier,
+            self.ops_per_inference,
+        ]
+    }
+    
+    // Inverse of field_values, with no accuracy_delta entries and no DVFS points
+    fn from_field_values(values: [f64; FIELD_COUNT]) -> Self {
+        let [base_power_consumption, computation_factor, memory_usage, memory_power_factor, inference_time,
+             thermal_design_power, cache_size, memory_bandwidth, startup_energy, sparsity, onset_batch,
+             max_penalty, cold_start_penalty, training_compute_multiplier, ops_per_inference] = values;
+        ModelParams {
+            base_power_consumption,
+            computation_factor,
+            memory_usage,
+            memory_power_factor,
+            inference_time,
+            thermal_design_power,
+            cache_size,
+            memory_bandwidth,
+            startup_energy,
+            sparsity,
+            accuracy_delta: BTreeMap::new(),
+            thermal: ThermalModel { onset_batch, max_penalty },
+            cold_start_penalty,
+            training_compute_multiplier,
+            ops_per_inference,
+            dvfs_points: Vec::new(),
+        }
+    }
+    
+    // Reject physically impossible values (negative power, zero bandwidth, ...)
+    // that would otherwise propagate NaN or infinity through every calculation
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        for (meta, value) in FIELD_METADATA.iter().zip(self.field_values()) {
+            if !meta.accepts(value) {
+                return Err(ValidationError { field: meta.name, constraint: meta.constraint });
+            }
+        }
+        if self.accuracy_delta.values().any(|drop| !drop.is_finite()) {
+            return Err(ValidationError { field: "accuracy_delta", constraint: "must be finite" });
+        }
+        let valid_point = |point: &DvfsPoint| point.freq_ghz.is_finite() && point.voltage.is_finite() && point.freq_ghz > 0.0 && point.voltage > 0.0;
+        if !self.dvfs_points.iter().all(valid_point) {
+            return Err(ValidationError { field: "dvfs_points", constraint: "frequency and voltage must be finite and > 0" });
+        }
+        Ok(())
+    }
+}
+
+// Self-description of one numeric ModelParams field, e.g. for generating forms.
+// The valid range runs from min to max, each end open when its *_exclusive flag is set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FieldMeta {
+    pub name: &'static str,
+    pub unit: &'static str,       // e.g. "W", "GB/s"; "" for plain factors
+    pub default: f64,             // ModelParams::default() value, which reads it from here
+    pub min: f64,
+    pub max: f64,                 // infinity when unbounded
+    pub min_exclusive: bool,
+    pub max_exclusive: bool,
+    pub constraint: &'static str, // the range as ValidationError reports it, e.g. "must be > 0"
+}
+
+impl FieldMeta {
+    const fn non_negative(name: &'static str, unit: &'static str, default: f64) -> Self {
+        FieldMeta {
+            name,
+            unit,
+            default,
+            min: 0.0,
+            max: f64::INFINITY,
+            min_exclusive: false,
+            max_exclusive: false,
+            constraint: "must be >= 0",
+        }
+    }
+    
+    const fn positive(name: &'static str, unit: &'static str, default: f64) -> Self {
+        FieldMeta { min_exclusive: true, constraint: "must be > 0", ..FieldMeta::non_negative(name, unit, default) }
+    }
+    
+    // Whether a value lies in the valid range; NaN and infinities never do
+    pub fn accepts(&self, value: f64) -> bool {
+        let above_min = if self.min_exclusive { value > self.min } else { value >= self.min };
+        let below_max = if self.max_exclusive { value < self.max } else { value <= self.max };
+        value.is_finite() && above_min && below_max
+    }
+}
+
+const FIELD_COUNT: usize = 15;
+
+const FIELD_METADATA: [FieldMeta; FIELD_COUNT] = [
+    FieldMeta::non_negative("base_power_consumption", "W", 50.0),
+    FieldMeta::non_negative("computation_factor", "", 2.5),
+    FieldMeta::non_negative("memory_usage", "GB", 4.0),
+    FieldMeta::non_negative("memory_power_factor", "W/GB", 5.0),
+    FieldMeta::positive("inference_time", "s", 0.05),
+    FieldMeta::positive("thermal_design_power", "W", 100.0),
+    FieldMeta::non_negative("cache_size", "MB", 32.0),
+    FieldMeta::positive("memory_bandwidth", "GB/s", 256.0),
+    FieldMeta::non_negative("startup_energy", "J", 0.0),
+    FieldMeta {
+        max: 1.0,
+        max_exclusive: true,
+        constraint: "must be in [0, 1)",
+        ..FieldMeta::non_negative("sparsity", "fraction", 0.0)
+    },
+    FieldMeta::positive("thermal.onset_batch", "samples", 64.0),
+    FieldMeta::non_negative("thermal.max_penalty", "fraction", 0.25),
+    FieldMeta { min: 1.0, constraint: "must be >= 1", ..FieldMeta::non_negative("cold_start_penalty", "multiplier", 1.0) },
+    // Forward pass plus a backward pass costing about twice as much
+    FieldMeta::positive("training_compute_multiplier", "multiplier", 3.0),
+    // About 4.1 GMACs per image for ResNet-50, counting a multiply-accumulate as two ops
+    FieldMeta::non_negative("ops_per_inference", "ops", 8.2e9),
+];
+
+// Builder for ModelParams; unset fields keep their ModelParams::default() values
+#[derive(Clone, Debug)]
+pub struct ModelParamsBuilder {
+    params: ModelParams,
+}
+
+impl ModelParamsBuilder {
+    fn new() -> Self {
+        ModelParamsBuilder {
+            params: ModelParams::default(),
+        }
+    }
+    
+    pub fn base_power_consumption(mut self, watts: f64) -> Self {
+        self.params.base_power_consumption = watts;
+        self
+    }
+    
+    pub fn computation_factor(mut self, factor: f64) -> Self {
+        self.params.computation_factor = factor;
+        self
+    }
+    
+    pub fn memory_usage(mut self, gigabytes: f64) -> Self {
+        self.params.memory_usage = gigabytes;
+        self
+    }
+    
+    pub fn memory_power_factor(mut self, watts_per_gb: f64) -> Self {
+        self.params.memory_power_factor = watts_per_gb;
+        self
+    }
+    
+    pub fn inference_time(mut self, seconds: f64) -> Self {
+        self.params.inference_time = seconds;
+        self
+    }
+    
+    pub fn thermal_design_power(mut self, watts: f64) -> Self {
+        self.params.thermal_design_power = watts;
+        self
+    }
+    
+    pub fn cache_size(mut self, megabytes: f64) -> Self {
+        self.params.cache_size = megabytes;
+        self
+    }
+    
+    pub fn memory_bandwidth(mut self, gb_per_second: f64) -> Self {
+        self.params.memory_bandwidth = gb_per_second;
+        self
+    }
+    
+    pub fn startup_energy(mut self, joules: f64) -> Self {
+        self.params.startup_energy = joules;
+        self
+    }
+    
+    pub fn sparsity(mut self, fraction: f64) -> Self {
+        self.params.sparsity = fraction;
+        self
+    }
+    
+    // Record the accuracy drop (percentage points) of running at this precision
+    pub fn accuracy_delta(mut self, precision: Precision, drop: f64) -> Self {
+        self.params.accuracy_delta.insert(precision, drop);
+        self
+    }
+    
+    pub fn cold_start_penalty(mut self, multiplier: f64) -> Self {
+        self.params.cold_start_penalty = multiplier;
+        self
+    }
+    
+    pub fn training_compute_multiplier(mut self, multiplier: f64) -> Self {
+        self.params.training_compute_multiplier = multiplier;
+        self
+    }
+    
+    pub fn ops_per_inference(mut self, ops: f64) -> Self {
+        self.params.ops_per_inference = ops;
+        self
+    }
+    
+    // Add a selectable DVFS operating point
+    pub fn dvfs_point(mut self, point: DvfsPoint) -> Self {
+        self.params.dvfs_points.push(point);
+        self
+    }
+    
+    pub fn thermal(mut self, thermal: ThermalModel) -> Self {
+        self.params.thermal = thermal;
+        self
+    }
+    
+    // Finish building, rejecting physically impossible values
+    pub fn build(self) -> Result<ModelParams, ValidationError> {
+        self.params.validate()?;
+        Ok(self.params)
+    }
+}
+
+// A ModelParams field that violates its physical constraint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    pub field: &'static str,      // name of the offending field
+    pub constraint: &'static str, // e.g. "must be > 0"
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.field, self.constraint)
+    }
+}
+
+impl Error for ValidationError {}
+
+// Failure loading or saving a hardware profile file
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum ProfileError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProfileError::Io(error) => write!(f, "profile I/O error: {}", error),
+            ProfileError::Json(error) => write!(f, "invalid profile JSON: {}", error),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Error for ProfileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProfileError::Io(error) => Some(error),
+            ProfileError::Json(error) => Some(error),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<std::io::Error> for ProfileError {
+    fn from(error: std::io::Error) -> Self {
+        ProfileError::Io(error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for ProfileError {
+    fn from(error: serde_json::Error) -> Self {
+        ProfileError::Json(error)
+    }
+}
+
+// Named hardware classes with ready-made ModelParams, as a starting point before
+// calibrating against measurements with ModelParams::fit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HardwarePreset {
+    EdgeTpu,       // USB/M.2 edge accelerator (Coral Edge TPU class)
+    DesktopGpu,    // consumer discrete GPU (RTX 4090 class)
+    DatacenterGpu, // SXM datacenter GPU (H100 class)
+    CpuServer,     // dual-socket server CPU (Xeon / EPYC class), no accelerator
+}
+
+impl HardwarePreset {
+    // Every preset, in declaration order
+    pub const ALL: [HardwarePreset; 4] = [
+        HardwarePreset::EdgeTpu,
+        HardwarePreset::DesktopGpu,
+        HardwarePreset::DatacenterGpu,
+        HardwarePreset::CpuServer,
+    ];
+    
+    // Parameters for this hardware class. TDP, cache, and bandwidth follow the vendor
+    // datasheets of the named reference parts; base power is measured idle draw and
+    // memory_usage a model that fits comfortably in device memory. inference_time is
+    // the published single-sample latency of a typical model for the class, and
+    // computation_factor puts the energy-optimal batch where the class usually runs:
+    // a handful of samples on edge chips and CPUs, tens on GPUs.
+    pub fn params(self) -> ModelParams {
+        let builder = ModelParams::builder();
+        let builder = match self {
+            // Coral Edge TPU: 2 W peak, 8 MB on-chip SRAM for weights, host LPDDR4
+            // (~25.6 GB/s), ~0.5 W idle, MobileNet-class inference in ~5 ms
+            HardwarePreset::EdgeTpu => builder
+                .base_power_consumption(0.5)
+                .computation_factor(0.05)
+                .memory_usage(0.008)
+                .memory_power_factor(20.0)
+                .inference_time(0.005)
+                .thermal_design_power(2.0)
+                .cache_size(8.0)
+                .memory_bandwidth(25.6),
+            // RTX 4090: 450 W board power, 72 MB L2, 1008 GB/s GDDR6X, ~30 W idle;
+            // an 8 GB model at ~10 ms per sample. GDDR6X draws roughly 3 W per GB in use
+            HardwarePreset::DesktopGpu => builder
+                .base_power_consumption(30.0)
+                .computation_factor(0.8)
+                .memory_usage(8.0)
+                .memory_power_factor(3.0)
+                .inference_time(0.01)
+                .thermal_design_power(450.0)
+                .cache_size(72.0)
+                .memory_bandwidth(1008.0),
+            // H100 SXM: 700 W, 50 MB L2, 3350 GB/s HBM3, ~70 W idle; a 40 GB model at
+            // ~8 ms per sample. HBM is cheaper per GB moved than GDDR, ~2 W per GB
+            HardwarePreset::DatacenterGpu => builder
+                .base_power_consumption(70.0)
+                .computation_factor(1.0)
+                .memory_usage(40.0)
+                .memory_power_factor(2.0)
+                .inference_time(0.008)
+                .thermal_design_power(700.0)
+                .cache_size(50.0)
+                .memory_bandwidth(3350.0),
+            // Two 270 W server sockets, ~105 MB LLC, 8-channel DDR5-4800 (~307 GB/s),
+            // ~120 W idle for the platform; a 16 GB model at ~80 ms per sample.
+            // DDR5 RDIMMs draw ~0.4 W per GB
+            HardwarePreset::CpuServer => builder
+                .base_power_consumption(120.0)
+                .computation_factor(8.0)
+                .memory_usage(16.0)
+                .memory_power_factor(0.4)
+                .inference_time(0.08)
+                .thermal_design_power(540.0)
+                .cache_size(105.0)
+                .memory_bandwidth(307.0),
+        };
+        builder.build().expect("hardware presets are valid")
+    }
+}
+
+// One layer (or group of layers) of a network for Optimizer::layered_energy, e.g.
+// FP16 attention blocks next to INT8 feed-forward blocks
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayerSpec {
+    pub compute_factor: f64, // replaces computation_factor; also sets the layer's share of inference time
+    pub memory_usage: f64,   // GB of weights and activations this layer streams
+    pub precision: Precision,
+}
+
+// Numeric precision used to run inference
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum Precision {
+    Fp32,
+    Fp16,
+    Int8,
+    Int4,
+    Bf16,
+    Fp8,
+    Fp64,
+}
+
+impl Precision {
+    // Every supported precision, in declaration order
+    pub const ALL: [Precision; 7] = [
+        Precision::Fp32,
+        Precision::Fp16,
+        Precision::Int8,
+        Precision::Int4,
+        Precision::Bf16,
+        Precision::Fp8,
+        Precision::Fp64,
+    ];
+    
+    // Canonical lowercase name, as used in CSV column headers
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Precision::Fp32 => "fp32",
+            Precision::Fp16 => "fp16",
+            Precision::Int8 => "int8",
+            Precision::Int4 => "int4",
+            Precision::Bf16 => "bf16",
+            Precision::Fp8 => "fp8",
+            Precision::Fp64 => "fp64",
+        }
+    }
+}
+
+// Error returned when a string does not name a supported precision
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    input: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown precision \"{}\" (expected one of", self.input)?;
+        for (index, precision) in Precision::ALL.iter().enumerate() {
+            write!(f, "{} {}", if index == 0 { "" } else { "," }, precision.as_str())?;
+        }
+        write!(f, ", or a bit width: 64, 32, 16, 8, 4)")
+    }
+}
+
+impl Error for ParseError {}
+
+impl FromStr for Precision {
+    type Err = ParseError;
+    
+    // Validate string input (CSV, CLI) once at the boundary. Names match case-
+    // insensitively; a bare bit width picks the usual format of that width (16 is
+    // FP16 rather than BF16, 8 is INT8 rather than FP8).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "fp32" | "32" => Ok(Precision::Fp32),
+            "fp16" | "16" => Ok(Precision::Fp16),
+            "int8" | "8" => Ok(Precision::Int8),
+            "int4" | "4" => Ok(Precision::Int4),
+            "bf16" => Ok(Precision::Bf16),
+            "fp8" => Ok(Precision::Fp8),
+            "fp64" | "64" => Ok(Precision::Fp64),
+            _ => Err(ParseError { input: s.to_string() }),
+        }
+    }
+}
+
+impl TryFrom<&str> for Precision {
+    type Error = ParseError;
+    
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+// The canonical lowercase name, as in as_str
+impl fmt::Display for Precision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Per-precision scaling used by the energy model: compute power and memory traffic
+// relative to FP32, and the harmonic count of the advanced Fourier term
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecisionFactors {
+    pub compute: f64,          // computation power factor (FP32 = 1.0)
+    pub memory_bandwidth: f64, // memory bandwidth factor (FP32 = 1.0)
+    pub harmonics: f64,        // harmonics in fourier_energy_transform
+}
+
+impl PrecisionFactors {
+    // Built-in factors for a precision
+    pub fn default_for(precision: Precision) -> Self {
+        let (compute, memory_bandwidth, harmonics) = match precision {
+            Precision::Fp16 => (0.65, 0.8, 16.0),  // FP16: slightly higher energy, better memory efficiency
+            Precision::Int8 => (0.35, 0.6, 8.0),   // INT8: lower energy, reduced memory bandwidth
+            Precision::Int4 => (0.2, 0.4, 4.0),    // INT4: very low energy, significant bandwidth reduction
+            Precision::Bf16 => (0.65, 0.85, 16.0), // BF16: FP16 compute, keeps FP32 exponent range so no bandwidth savings over FP16
+            Precision::Fp8 => (0.28, 0.5, 8.0),    // FP8 (E4M3): between INT8 and INT4
+            Precision::Fp64 => (1.8, 1.3, 64.0),   // FP64: above baseline, twice the bytes per value
+            Precision::Fp32 => (1.0, 1.0, 32.0),   // FP32: baseline
+        };
+        PrecisionFactors { compute, memory_bandwidth, harmonics }
+    }
+}
+
+// Precision factors with per-precision overrides on top of the built-in defaults,
+// e.g. for a chip whose INT8 path is unusually efficient
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrecisionProfile {
+    overrides: Vec<(Precision, PrecisionFactors)>,
+}
+
+impl PrecisionProfile {
+    // Replace the factors for one precision
+    pub fn with(mut self, precision: Precision, factors: PrecisionFactors) -> Self {
+        self.overrides.retain(|&(existing, _)| existing != precision);
+        self.overrides.push((precision, factors));
+        self
+    }
+    
+    // Factors in effect for a precision: its override, or the built-in default
+    pub fn get(&self, precision: Precision) -> PrecisionFactors {
+        self.overrides
+            .iter()
+            .find(|&&(existing, _)| existing == precision)
+            .map_or_else(|| PrecisionFactors::default_for(precision), |&(_, factors)| factors)
+    }
+    
+    // Reject negative or NaN overrides, which would make energy negative
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        for (_, factors) in &self.overrides {
+            let fields = [
+                ("precision compute factor", factors.compute),
+                ("precision memory bandwidth factor", factors.memory_bandwidth),
+                ("precision harmonics", factors.harmonics),
+            ];
+            for (field, value) in fields {
+                if value.is_nan() || value < 0.0 {
+                    return Err(ValidationError { field, constraint: "must be >= 0" });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    type Corruption = fn(&mut ModelParams);
+    
+    #[test]
+    fn each_out_of_range_field_is_reported_by_name() {
+        let cases: [(&str, Corruption); 18] = [
+            ("base_power_consumption", |p| p.base_power_consumption = -1.0),
+            ("computation_factor", |p| p.computation_factor = -0.1),
+            ("memory_usage", |p| p.memory_usage = f64::NAN),
+            ("memory_power_factor", |p| p.memory_power_factor = -5.0),
+            ("inference_time", |p| p.inference_time = 0.0),
+            ("thermal_design_power", |p| p.thermal_design_power = 0.0),
+            ("cache_size", |p| p.cache_size = -32.0),
+            ("memory_bandwidth", |p| p.memory_bandwidth = 0.0),
+            ("startup_energy", |p| p.startup_energy = -1.0),
+            ("sparsity", |p| p.sparsity = 1.0),
+            ("thermal.onset_batch", |p| p.thermal.onset_batch = 0.0),
+            ("thermal.max_penalty", |p| p.thermal.max_penalty = -0.25),
+            ("cold_start_penalty", |p| p.cold_start_penalty = 0.5),
+            ("training_compute_multiplier", |p| p.training_compute_multiplier = 0.0),
+            ("ops_per_inference", |p| p.ops_per_inference = -1.0),
+            ("accuracy_delta", |p| {
+                p.accuracy_delta.insert(Precision::Int8, f64::NAN);
+            }),
+            ("dvfs_points", |p| p.dvfs_points.push(DvfsPoint { freq_ghz: 0.0, voltage: 0.9 })),
+            ("dvfs_points", |p| p.dvfs_points.push(DvfsPoint { freq_ghz: 1.5, voltage: -0.9 })),
+        ];
+        assert_eq!(ModelParams::default().validate(), Ok(()));
+        for (field, corrupt) in cases {
+            let mut params = ModelParams::default();
+            corrupt(&mut params);
+            assert_eq!(params.validate().map_err(|error| error.field), Err(field));
+        }
+    }
+    
+    #[test]
+    fn defaults_come_from_the_field_metadata() {
+        let defaults: Vec<f64> = ModelParams::field_metadata().iter().map(|meta| meta.default).collect();
+        assert_eq!(ModelParams::default().field_values().to_vec(), defaults);
+        assert_eq!(ThermalModel::default(), ThermalModel { onset_batch: 64.0, max_penalty: 0.25 });
+        assert_eq!(ModelParams::builder().build(), Ok(ModelParams::default()));
+    }
+    
+    #[test]
+    fn infinite_values_are_rejected() {
+        for meta in ModelParams::field_metadata() {
+            assert!(meta.accepts(meta.default), "{}", meta.name);
+            assert!(!meta.accepts(f64::INFINITY) && !meta.accepts(f64::NEG_INFINITY) && !meta.accepts(f64::NAN), "{}", meta.name);
+        }
+        let params = ModelParams::builder().memory_bandwidth(f64::INFINITY).build();
+        assert_eq!(params.map_err(|error| error.field), Err("memory_bandwidth"));
+    }
+    
+    #[test]
+    fn every_precision_round_trips_through_its_name() {
+        for precision in Precision::ALL {
+            assert_eq!(precision.as_str().parse::<Precision>(), Ok(precision));
+            assert_eq!(Precision::try_from(precision.to_string().as_str()), Ok(precision));
+            assert_eq!(precision.as_str().to_ascii_uppercase().parse::<Precision>(), Ok(precision));
+        }
+        assert_eq!("Bf16".parse::<Precision>(), Ok(Precision::Bf16));
+    }
+    
+    #[test]
+    fn bit_widths_pick_the_usual_format() {
+        let aliases = [("64", Precision::Fp64), ("32", Precision::Fp32), ("16", Precision::Fp16), ("8", Precision::Int8), ("4", Precision::Int4)];
+        for (width, precision) in aliases {
+            assert_eq!(width.parse::<Precision>(), Ok(precision));
+        }
+        for input in ["2", "fp4", "", " fp16"] {
+            assert_eq!(input.parse::<Precision>(), Err(ParseError { input: input.to_string() }));
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// Incremental batch-size tuning for a live service whose request rate changes
+use crate::model::Precision;
+use crate::optimizer::{check_scan_range, Optimizer, OptimizerError};
+
+// Tracks the most efficient batch size that keeps up with the current load
+// (throughput >= offered requests per second). Each update searches only
+// +/- window around the previous optimum and rescans the full range only when
+// the best local candidate sits on the window's edge or none keeps up.
+#[derive(Debug, Clone)]
+pub struct OnlineOptimizer {
+    optimizer: Optimizer,
+    precision: Precision,
+    min_batch: u32,
+    max_batch: u32,
+    window: u32,
+    last_optimum: Option<(u32, f64)>,
+    full_scans: u32,
+}
+
+impl OnlineOptimizer {
+    pub fn new(optimizer: Optimizer, precision: Precision, min_batch: u32, max_batch: u32, window: u32) -> Self {
+        OnlineOptimizer { optimizer, precision, min_batch, max_batch, window, last_optimum: None, full_scans: 0 }
+    }
+    
+    // Re-tune for a load in requests per second. Returns (batch_size, efficiency),
+    // or None when no batch in the range reaches that throughput.
+    pub fn update(&mut self, current_load: f64) -> Result<Option<(u32, f64)>, OptimizerError> {
+        let local = match self.last_optimum {
+            Some((batch_size, _)) => {
+                let low = batch_size.saturating_sub(self.window).max(self.min_batch);
+                let high = batch_size.saturating_add(self.window).min(self.max_batch);
+                match self.best_feasible(low, high, current_load)? {
+                    // The optimum may lie past an edge that isn't the range's own bound
+                    Some((best, _)) if (best == low && low > self.min_batch) || (best == high && high < self.max_batch) => None,
+                    found => found,
+                }
+            }
+            None => None,
+        };
+        
+        let optimum = match local {
+            Some(optimum) => Some(optimum),
+            None => {
+                self.full_scans += 1;
+                self.best_feasible(self.min_batch, self.max_batch, current_load)?
+            }
+        };
+        if optimum.is_some() {
+            self.last_optimum = optimum;
+        }
+        Ok(optimum)
+    }
+    
+    // Optimum from the most recent successful update
+    pub fn current(&self) -> Option<(u32, f64)> {
+        self.last_optimum
+    }
+    
+    // How many updates fell back to scanning the whole range
+    pub fn full_scans(&self) -> u32 {
+        self.full_scans
+    }
+    
+    pub fn optimizer(&self) -> &Optimizer {
+        &self.optimizer
+    }
+    
+    // Most efficient batch in low..=high whose throughput meets the load; ties go to
+    // the smaller batch
+    fn best_feasible(&self, low: u32, high: u32, load: f64) -> Result<Option<(u32, f64)>, OptimizerError> {
+        check_scan_range(low, high)?;
+        let mut best: Option<(u32, f64)> = None;
+        
+        for batch_size in low..=high {
+            if self.optimizer.throughput(batch_size, self.precision)? < load {
+                continue;
+            }
+            let efficiency = self.optimizer.efficiency(batch_size, self.precision)?;
+            if best.is_none_or(|(_, best_efficiency)| efficiency > best_efficiency) {
+                best = Some((batch_size, efficiency));
+            }
+        }
+        
+        Ok(best)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ModelParams;
+    
+    #[test]
+    fn gradually_rising_load_moves_the_optimum_without_rescans() {
+        let optimizer = Optimizer::new(ModelParams::default()).unwrap();
+        let mut online = OnlineOptimizer::new(optimizer.clone(), Precision::Fp32, 1, 256, 4);
+        let mut previous = online.update(10.0).unwrap().unwrap().0;
+        assert_eq!(online.full_scans(), 1);
+        
+        for step in 1..=56 {
+            let load = 10.0 + step as f64 * 0.25;
+            let (batch_size, efficiency) = online.update(load).unwrap().unwrap();
+            assert!(batch_size >= previous && batch_size - previous <= 2, "load {}: {} after {}", load, batch_size, previous);
+            
+            let mut fresh = OnlineOptimizer::new(optimizer.clone(), Precision::Fp32, 1, 256, 4);
+            assert_eq!(fresh.update(load).unwrap(), Some((batch_size, efficiency)));
+            previous = batch_size;
+        }
+        assert!(previous > 15);
+        assert_eq!(online.full_scans(), 1);
+        
+        // No batch keeps up with this load; the last optimum is kept
+        assert_eq!(online.update(1e6).unwrap(), None);
+        assert_eq!(online.current().map(|(batch_size, _)| batch_size), Some(previous));
+    }
+}
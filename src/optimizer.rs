@@ -0,0 +1,2457 @@
+#[cfg(all(feature = "serde", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+#[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::Write;
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::FloatMath;
+use crate::model::{HardwarePreset, LayerSpec, ModelParams, Precision, PrecisionProfile, ValidationError};
+use crate::units::{BatchSize, Energy};
+
+// Errors returned by optimizer calculations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptimizerError {
+    ZeroBatchSize,         // batch size must be at least 1
+    NegativeGridIntensity, // grid carbon intensity below zero
+    NegativePrice,         // electricity price below zero
+    ZeroSampleCount,       // amortizing over zero samples
+    InvalidPrecisionMix,   // mixed-precision fractions negative or not summing to 1
+    InvalidEnergyWeight,   // energy/throughput weight outside [0, 1]
+    RangeTooLarge,         // exhaustive sweep over more than MAX_SCAN_BATCHES batch sizes
+    ZeroDeviceCount,       // sharding across zero devices
+    NonPositiveBandwidth,  // interconnect bandwidth not above zero
+    WindowTooShort,        // requests need more time than the serving window has
+    InvalidLayerSpec,      // layer factors negative or NaN, or no layer does any compute
+    InvalidServingLoad,    // arrival rate not above zero or negative batch wait
+    Overloaded,            // arrivals outpace throughput even at the largest batch
+    InvalidSaturationFraction, // memory saturation target outside (0, 1)
+    InvalidAcceptanceRate, // speculative acceptance rate outside [0, 1]
+    InvalidDvfsPoint,      // DVFS point index past the end of dvfs_points
+    SamplesExceedBatch,    // more real samples than slots in the batch
+    EmptyRange,            // min_batch above max_batch
+    InvalidTemperature,    // at or below absolute zero, or not finite
+    InvalidEmbodiedCarbon, // embodied carbon negative or not finite
+    InvalidAccuracyDrop,   // accuracy drop limit negative or NaN
+}
+
+impl fmt::Display for OptimizerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptimizerError::ZeroBatchSize => write!(f, "batch size must be at least 1"),
+            OptimizerError::NegativeGridIntensity => write!(f, "grid carbon intensity must not be negative"),
+            OptimizerError::NegativePrice => write!(f, "electricity price must not be negative"),
+            OptimizerError::ZeroSampleCount => write!(f, "total sample count must be at least 1"),
+            OptimizerError::InvalidPrecisionMix => write!(f, "precision fractions must be non-negative and sum to 1"),
+            OptimizerError::InvalidEnergyWeight => write!(f, "energy weight must be between 0 and 1"),
+            OptimizerError::ZeroDeviceCount => write!(f, "device count must be at least 1"),
+            OptimizerError::NonPositiveBandwidth => write!(f, "interconnect bandwidth must be positive"),
+            OptimizerError::WindowTooShort => write!(f, "requests do not fit in the serving window"),
+            OptimizerError::InvalidServingLoad => write!(f, "arrival rate must be positive and batch wait non-negative"),
+            OptimizerError::Overloaded => write!(f, "arrival rate exceeds throughput at the largest batch size"),
+            OptimizerError::EmptyRange => write!(f, "batch range is empty: min_batch exceeds max_batch"),
+            OptimizerError::SamplesExceedBatch => write!(f, "actual samples must not exceed the batch size"),
+            OptimizerError::InvalidDvfsPoint => write!(f, "DVFS point index is out of range"),
+            OptimizerError::InvalidAcceptanceRate => write!(f, "acceptance rate must be between 0 and 1"),
+            OptimizerError::InvalidSaturationFraction => write!(f, "saturation fraction must be between 0 and 1, exclusive"),
+            OptimizerError::InvalidLayerSpec => write!(f, "layer factors must be non-negative, with at least one computing layer"),
+            OptimizerError::RangeTooLarge => write!(f, "batch range spans more than {} sizes; use optimize_batch_size_fast", MAX_SCAN_BATCHES),
+            OptimizerError::InvalidTemperature => write!(f, "temperature must be finite and above absolute zero (-273.15°C)"),
+            OptimizerError::InvalidEmbodiedCarbon => write!(f, "embodied carbon must be finite and non-negative"),
+            OptimizerError::InvalidAccuracyDrop => write!(f, "accuracy drop limit must not be negative"),
+        }
+    }
+}
+
+impl Error for OptimizerError {}
+
+#[cfg(feature = "std")]
+impl From<OptimizerError> for std::io::Error {
+    fn from(error: OptimizerError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, error)
+    }
+}
+
+// Power drawn at an operating point before and after the TDP cap
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerDraw {
+    pub unclamped_power: f64, // watts the workload demands without a thermal limit
+    pub clamped_power: f64,   // watts actually drawn, at most thermal_design_power
+}
+
+impl PowerDraw {
+    // Whether the thermal limit is throttling this operating point
+    pub fn is_throttled(&self) -> bool {
+        self.unclamped_power > self.clamped_power
+    }
+}
+
+// Which roof limits a batch in the roofline model
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    Compute, // arithmetic dominates; lower precision or a faster chip helps
+    Memory,  // streaming the weights dominates; more memory bandwidth helps
+}
+
+// Components of the energy spent on one batch
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EnergyBreakdown {
+    pub base_power: f64,        // watts
+    pub computation_power: f64, // watts
+    pub memory_power: f64,      // watts
+    pub inference_time: f64,    // seconds per batch
+    pub total_energy: f64,      // joules
+}
+
+impl EnergyBreakdown {
+    fn new(base_power: f64, computation_power: f64, memory_power: f64, inference_time: f64) -> Self {
+        EnergyBreakdown {
+            base_power,
+            computation_power,
+            memory_power,
+            inference_time,
+            total_energy: (base_power + computation_power + memory_power) * inference_time,
+        }
+    }
+    
+    // Total power draw (watts)
+    pub fn total_power(&self) -> f64 {
+        self.base_power + self.computation_power + self.memory_power
+    }
+}
+
+// Elasticity of batch energy with respect to each ModelParams field at one operating
+// point: (dE / E) / (dp / p), so 0.5 means a 1% larger field costs 0.5% more energy.
+// Sorted by magnitude, most influential field first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SensitivityReport {
+    pub energy: f64,                              // joules at the unperturbed parameters
+    pub sensitivities: Vec<(&'static str, f64)>, // (field name, elasticity)
+}
+
+impl SensitivityReport {
+    // Field with the largest elasticity magnitude
+    pub fn most_sensitive(&self) -> Option<(&'static str, f64)> {
+        self.sensitivities.first().copied()
+    }
+}
+
+// Energy saved by switching one batch from one precision to another. A negative
+// saving means the target precision costs more.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SavingsReport {
+    pub joules_saved: f64,     // energy(from) - energy(to)
+    pub percent_saved: f64,    // joules_saved as a percentage of energy(from)
+    pub efficiency_ratio: f64, // efficiency(to) / efficiency(from)
+}
+
+// Outcome of an A/B efficiency comparison between two batch sizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preference {
+    A,     // the first batch size is more efficient
+    B,     // the second batch size is more efficient
+    Equal, // efficiencies agree to within EFFICIENCY_TOLERANCE
+}
+
+// Efficiency of two batch sizes side by side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchComparison {
+    pub preferred: Preference,
+    pub efficiency_a: f64,       // samples per joule at batch a
+    pub efficiency_b: f64,       // samples per joule at batch b
+    pub percent_difference: f64, // (efficiency_a - efficiency_b) / efficiency_b * 100; positive when a wins
+}
+
+// One operating point's headline numbers, for logging. Displays as
+// "batch=32 fp16: 122.9 J (3.84 J/sample, 0.260 samples/J, 1229 ms, 100 W)"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EstimateSummary {
+    pub batch_size: u32,
+    pub precision: Precision,
+    pub energy: f64,            // joules per batch
+    pub energy_per_sample: f64, // joules per sample
+    pub efficiency: f64,        // samples per joule
+    pub latency: f64,           // seconds per batch
+    pub power: f64,             // watts drawn, after the TDP cap
+}
+
+impl fmt::Display for EstimateSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "batch={} {}: {:.1} J ({:.2} J/sample, {:.3} samples/J, {:.0} ms, {:.0} W)",
+            self.batch_size, self.precision.as_str(), self.energy, self.energy_per_sample,
+            self.efficiency, self.latency * 1000.0, self.power)
+    }
+}
+
+// One precision's line of a PrecisionTable
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecisionRow {
+    pub precision: Precision,
+    pub energy: f64,     // joules per batch
+    pub efficiency: f64, // samples per joule
+    pub throughput: f64, // samples per second
+    pub latency: f64,    // seconds per batch
+}
+
+// Several precisions side by side at one batch size. Displays as an aligned text
+// table, one row per precision in the order requested:
+//   precision    energy (J)   samples/J   samples/s   latency (ms)
+//   fp32           143.1550      0.2235     22.3534         1431.6
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrecisionTable {
+    pub batch_size: u32,
+    pub rows: Vec<PrecisionRow>,
+}
+
+impl fmt::Display for PrecisionTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:<9} {:>13} {:>11} {:>11} {:>14}", "precision", "energy (J)", "samples/J", "samples/s", "latency (ms)")?;
+        for row in &self.rows {
+            write!(f, "\n{:<9} {:>13.4} {:>11.4} {:>11.4} {:>14.1}",
+                row.precision.as_str(), row.energy, row.efficiency, row.throughput, row.latency * 1000.0)?;
+        }
+        Ok(())
+    }
+}
+
+// Shape of the efficiency curve around its peak: a wide plateau means batch size
+// can be mis-chosen cheaply, a narrow one that it must be tuned carefully
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfileStats {
+    pub optimal_batch: u32,
+    pub peak_efficiency: f64, // samples per joule at optimal_batch
+    pub plateau_min: u32,     // smallest batch of the contiguous run within PLATEAU_FRACTION of peak
+    pub plateau_max: u32,     // largest batch of that run
+    pub plateau_width: u32,   // plateau_max - plateau_min + 1
+}
+
+// Distribution of efficiency (samples per joule) over the batch sizes of a sweep
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EfficiencyStats {
+    pub mean: f64,
+    pub median: f64,
+    pub p95: f64,     // 95th percentile, linearly interpolated between batch sizes
+    pub std_dev: f64, // population standard deviation
+}
+
+// Steady state of dynamic batching under a request arrival rate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ServeStats {
+    pub batch_size: u32,         // batch size that forms on average
+    pub average_latency: f64,    // seconds from arrival to result: batching wait plus compute
+    pub energy_per_request: f64, // joules, including idle power between batches
+    pub utilization: f64,        // fraction of time spent computing
+}
+
+// Replica count and batch size that serve a request rate with the least energy
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FleetPlan {
+    pub replicas: u32,
+    pub batch_size: u32,         // per-replica batch size
+    pub total_power: f64,        // watts across the fleet, i.e. joules per second of serving
+    pub energy_per_request: f64, // joules, including idle power between batches
+}
+
+// Energy split between the two phases of an LLM request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LlmEnergyBreakdown {
+    pub prefill_energy: f64, // joules spent processing the prompt
+    pub decode_energy: f64,  // joules spent generating tokens
+    pub total_energy: f64,   // joules
+}
+
+// Batch-independent terms of power_demand for one precision, hoisted out of the
+// per-batch math so sweeps like energy_range look them up once
+struct DemandCurve {
+    precision_factor: f64,
+    memory_bandwidth_factor: f64,
+    saturation_knee: f64,
+    sparsity_scale: f64,
+    thermal_onset: f64,
+    max_thermal_penalty: f64,
+    computation_factor: f64,
+    memory_power_scale: f64, // memory_usage * memory_power_factor
+    base_power_consumption: f64,
+    cache_knee: f64,
+    inference_time: f64,
+    cache_penalty: f64, // divides cache efficiency; 1.0 for a warm cache
+}
+
+impl DemandCurve {
+    // Power components and inference time demanded at a batch size (>= 1)
+    fn demand(&self, batch_size_f64: f64) -> EnergyBreakdown {
+        // Dynamic voltage scaling factor (modern processors scale voltage with load)
+        let voltage_scaling = 1.0 + (batch_size_f64.ln() * 0.05).min(0.3);
+        
+        let memory_saturation = 1.0 - (-batch_size_f64 / self.saturation_knee).exp();
+        let effective_memory_factor = self.memory_bandwidth_factor * memory_saturation;
+        
+        // Enhanced computation power with thermal throttling
+        let thermal_factor = 1.0 + (batch_size_f64 / self.thermal_onset).min(self.max_thermal_penalty); // Thermal throttling at high loads
+        let computation_power = self.computation_factor 
+            * batch_size_f64.powf(0.85) // Slightly less efficient scaling
+            * self.precision_factor 
+            * voltage_scaling
+            * thermal_factor
+            * self.sparsity_scale;
+        
+        // Enhanced memory power with bandwidth considerations
+        let memory_power = self.memory_power_scale
+            * effective_memory_factor
+            * (1.0 + batch_size_f64.powf(0.3) * 0.1) // Memory power increases with batch size
+            * self.sparsity_scale;
+        
+        // Base power with idle efficiency
+        let base_power = self.base_power_consumption 
+            * (0.8 + 0.2 * (batch_size_f64 / 100.0).min(1.0)); // Base power scales slightly with utilization
+        
+        // Enhanced inference time with memory and cache effects
+        let cache_efficiency = (1.0 - MAX_CACHE_PENALTY * (1.0 - (-batch_size_f64 / self.cache_knee).exp()))
+            / self.cache_penalty;
+        let inference_time = self.inference_time 
+            * batch_size_f64.powf(0.75) // Better scaling due to vectorization
+            * (2.0 - effective_memory_factor) // Memory bandwidth affects timing
+            / cache_efficiency; // Cache efficiency impact
+        
+        EnergyBreakdown::new(base_power, computation_power, memory_power, inference_time)
+    }
+}
+
+// One batch size of the JSON export, with metrics keyed by precision name
+#[cfg(all(feature = "serde", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+#[derive(serde::Serialize)]
+struct JsonRecord {
+    batch_size: u32,
+    energy: BTreeMap<&'static str, f64>,
+    efficiency: BTreeMap<&'static str, f64>,
+}
+
+// Precisions written by export_data and export_json
+#[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+const DEFAULT_EXPORT_PRECISIONS: [Precision; 4] = [Precision::Fp32, Precision::Fp16, Precision::Int8, Precision::Int4];
+
+// Relative efficiency gap below which compare_batches calls two batch sizes equal
+const EFFICIENCY_TOLERANCE: f64 = 1e-9;
+
+// KV cache footprint per token of context (GB), roughly a 7B model at FP16
+const KV_CACHE_GB_PER_TOKEN: f64 = 0.0005;
+
+// Draft tokens proposed per speculative decoding round before the target model verifies
+const SPECULATION_LENGTH: u32 = 4;
+
+// Joules in one kilowatt-hour
+const JOULES_PER_KWH: f64 = 3.6e6;
+
+// Service lifetime assumed for upgrade decisions: four years of continuous operation
+const HARDWARE_LIFETIME_SECONDS: f64 = 4.0 * 365.0 * 24.0 * 3600.0;
+
+// Memory bandwidth (GB/s) at which memory saturates with a knee at SATURATION_KNEE_BATCH
+const REFERENCE_MEMORY_BANDWIDTH: f64 = 256.0;
+const SATURATION_KNEE_BATCH: f64 = 32.0;
+
+// TDP (watts) at which ThermalModel::onset_batch applies unscaled
+const REFERENCE_THERMAL_DESIGN_POWER: f64 = 100.0;
+
+// Cache model: a REFERENCE_CACHE_SIZE (MB) cache loses efficiency at 1/CACHE_KNEE_BATCH per
+// sample of batch at first, up to MAX_CACHE_PENALTY once the working set far exceeds it
+const REFERENCE_CACHE_SIZE: f64 = 32.0;
+const CACHE_KNEE_BATCH: f64 = 128.0;
+const MAX_CACHE_PENALTY: f64 = 0.3;
+
+// Leakage model: base power is specified at REFERENCE_TEMPERATURE_C (°C) and follows
+// an Arrhenius law exp(-Ea/kT). An activation energy of 0.55 eV doubles leakage
+// between 25 and 35°C; the doubling interval widens to about 14°C near 85°C.
+const REFERENCE_TEMPERATURE_C: f64 = 25.0;
+const LEAKAGE_ACTIVATION_ENERGY_EV: f64 = 0.55;
+const BOLTZMANN_CONSTANT_EV: f64 = 8.617_333e-5; // eV/K
+const CELSIUS_TO_KELVIN: f64 = 273.15;
+
+// A training step keeps gradients the size of the weights and the activations saved
+// for the backward pass resident alongside them, about twice the inference footprint
+const TRAINING_MEMORY_FACTOR: f64 = 2.0;
+
+// Sparse kernels skip pruned weights but pay indexing and load-imbalance overhead,
+// so compute and memory power shrink with (1 - sparsity)^SPARSITY_EXPONENT rather
+// than linearly: 50% sparsity gives about a 1.4x saving instead of 2x
+const SPARSITY_EXPONENT: f64 = 0.5;
+
+// How far mixed-precision fractions may stray from summing to exactly 1
+const PRECISION_MIX_TOLERANCE: f64 = 1e-6;
+
+// Fraction of thermal design power above which sustained load throttles the chip
+const THERMAL_THROTTLE_THRESHOLD: f64 = 0.9;
+
+// Relative perturbation of each field in sensitivity's central differences
+const SENSITIVITY_STEP: f64 = 1e-4;
+
+// Picks one numeric field out of ModelParams, for perturbing it in place
+type FieldAccessor = fn(&mut ModelParams) -> &mut f64;
+
+// Efficiency, relative to the peak, that still counts as on the plateau
+const PLATEAU_FRACTION: f64 = 0.95;
+
+// Link energy of moving data between devices, about 10 pJ/bit
+const INTERCONNECT_JOULES_PER_GB: f64 = 0.08;
+
+// Longest batch range the exhaustive scans accept. A full u32 range would take minutes
+// (and efficiency_curve gigabytes); optimize_batch_size_fast handles any range.
+const MAX_SCAN_BATCHES: u64 = 10_000_000;
+
+// Relative standard deviation of simulated power-meter readings (1%)
+#[cfg(feature = "rand")]
+const DEFAULT_MEASUREMENT_NOISE: f64 = 0.01;
+
+// AI Energy Efficiency Optimizer
+#[derive(Clone, Debug)]
+pub struct Optimizer {
+    pub(crate) model_params: ModelParams,
+    pub(crate) precision_profile: PrecisionProfile,
+    pub(crate) calibration_scale: f64, // multiplies advanced_energy_formula; see calibrate_advanced
+    #[cfg(feature = "rand")]
+    pub(crate) measurement_noise: f64, // relative std dev of energy_consumption_noisy
+}
+
+impl Optimizer {
+    // Create a new optimizer with given model parameters
+    pub fn new(model_params: ModelParams) -> Result<Self, ValidationError> {
+        model_params.validate()?;
+        Ok(Optimizer {
+            model_params,
+            precision_profile: PrecisionProfile::default(),
+            calibration_scale: 1.0,
+            #[cfg(feature = "rand")]
+            measurement_noise: DEFAULT_MEASUREMENT_NOISE,
+        })
+    }
+    
+    // Optimizer for a named hardware class; see HardwarePreset::params for the numbers
+    pub fn from_preset(preset: HardwarePreset) -> Self {
+        Optimizer::new(preset.params()).expect("hardware presets are valid")
+    }
+    
+    pub fn params(&self) -> &ModelParams {
+        &self.model_params
+    }
+    
+    // Swap in new model parameters, validated as in new. The precision profile,
+    // advanced calibration scale, and measurement noise are kept.
+    pub fn set_params(&mut self, model_params: ModelParams) -> Result<(), ValidationError> {
+        model_params.validate()?;
+        self.model_params = model_params;
+        Ok(())
+    }
+    
+    // Use custom precision factors instead of the built-in table
+    pub fn with_precision_profile(mut self, profile: PrecisionProfile) -> Result<Self, ValidationError> {
+        profile.validate()?;
+        self.precision_profile = profile;
+        Ok(self)
+    }
+    
+    pub fn precision_profile(&self) -> &PrecisionProfile {
+        &self.precision_profile
+    }
+    
+    // (compute, memory bandwidth) factors for a precision under the active profile
+    fn precision_factors(&self, precision: Precision) -> (f64, f64) {
+        let factors = self.precision_profile.get(precision);
+        (factors.compute, factors.memory_bandwidth)
+    }
+    
+    // Calculate energy consumption for inference with enhanced formulas
+    pub fn energy_consumption(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        Ok(self.energy_breakdown(batch_size, precision)?.total_energy)
+    }
+    
+    // energy_consumption as a typed Energy, for callers reporting in Wh or kWh
+    pub fn energy_consumption_typed(&self, batch_size: u32, precision: Precision) -> Result<Energy, OptimizerError> {
+        Ok(Energy::joules(self.energy_consumption(batch_size, precision)?))
+    }
+    
+    // Set the relative standard deviation of energy_consumption_noisy (0.01 = 1%)
+    #[cfg(feature = "rand")]
+    pub fn with_measurement_noise(mut self, relative_std_dev: f64) -> Result<Self, ValidationError> {
+        if relative_std_dev.is_nan() || relative_std_dev < 0.0 {
+            return Err(ValidationError { field: "measurement_noise", constraint: "must be >= 0" });
+        }
+        self.measurement_noise = relative_std_dev;
+        Ok(self)
+    }
+    
+    // energy_consumption with multiplicative Gaussian noise, as a power meter would
+    // read it. Deterministic for a seeded rng; never negative.
+    #[cfg(feature = "rand")]
+    pub fn energy_consumption_noisy(&self, batch_size: u32, precision: Precision, rng: &mut impl Rng) -> Result<f64, OptimizerError> {
+        let energy = self.energy_consumption(batch_size, precision)?;
+        
+        // Box-Muller transform; 1 - u keeps the logarithm's argument in (0, 1]
+        let u1 = 1.0 - rng.random::<f64>();
+        let u2 = rng.random::<f64>();
+        let standard_normal = (-2.0 * u1.ln()).sqrt() * (2.0 * core::f64::consts::PI * u2).cos();
+        
+        Ok((energy * (1.0 + self.measurement_noise * standard_normal)).max(0.0))
+    }
+    
+    // Base, computation, and memory power plus inference time for one batch, with total
+    // power capped at thermal_design_power. Throttling scales every power component down
+    // and stretches inference time by the same ratio, so the batch's energy is unchanged.
+    pub fn energy_breakdown(&self, batch_size: u32, precision: Precision) -> Result<EnergyBreakdown, OptimizerError> {
+        Ok(self.energy_breakdown_for(BatchSize::try_from(batch_size)?, precision))
+    }
+    
+    // energy_breakdown for a batch size already known to be non-zero
+    pub fn energy_breakdown_for(&self, batch_size: BatchSize, precision: Precision) -> EnergyBreakdown {
+        self.apply_thermal_limit(self.power_demand(batch_size, precision))
+    }
+    
+    // energy_consumption for a batch size already known to be non-zero
+    pub fn energy_consumption_for(&self, batch_size: BatchSize, precision: Precision) -> f64 {
+        self.energy_breakdown_for(batch_size, precision).total_energy
+    }
+    
+    // efficiency for a batch size already known to be non-zero
+    pub fn efficiency_for(&self, batch_size: BatchSize, precision: Precision) -> f64 {
+        batch_size.get() as f64 / self.energy_consumption_for(batch_size, precision)
+    }
+    
+    // Energy (joules) of the first batch after loading, when caches are cold: cache
+    // efficiency is divided by cold_start_penalty, stretching inference time. Equals
+    // energy_consumption at the default penalty of 1.0. One-time load energy is
+    // separate (startup_energy).
+    pub fn energy_consumption_cold(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        let batch_size = BatchSize::try_from(batch_size)?;
+        let mut curve = self.demand_curve(self.precision_factors(precision));
+        curve.cache_penalty = self.model_params.cold_start_penalty;
+        Ok(self.apply_thermal_limit(curve.demand(batch_size.get() as f64)).total_energy)
+    }
+    
+    // Energy (joules) of one training step over a batch: forward and backward passes
+    // take training_compute_multiplier times the forward-pass time, and memory power
+    // covers TRAINING_MEMORY_FACTOR times memory_usage for gradients and activations.
+    // Optimizer-state updates are folded into the multiplier.
+    pub fn training_step_energy(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        let batch_size = BatchSize::try_from(batch_size)?;
+        let mut curve = self.demand_curve(self.precision_factors(precision));
+        curve.inference_time *= self.model_params.training_compute_multiplier;
+        curve.memory_power_scale *= TRAINING_MEMORY_FACTOR;
+        Ok(self.apply_thermal_limit(curve.demand(batch_size.get() as f64)).total_energy)
+    }
+    
+    // Energy (joules) at one of the model's DVFS operating points. The fastest listed
+    // point is the clock the base model describes; relative to it, dynamic compute power
+    // scales with voltage^2 * frequency and inference time with 1 / frequency, while
+    // base and memory power stay put. Slower points save compute energy but pay base
+    // power for longer.
+    pub fn energy_consumption_at_dvfs(&self, batch_size: u32, precision: Precision, point_index: usize) -> Result<f64, OptimizerError> {
+        let batch_size = BatchSize::try_from(batch_size)?;
+        let points = &self.model_params.dvfs_points;
+        let point = points.get(point_index).ok_or(OptimizerError::InvalidDvfsPoint)?;
+        let nominal = points
+            .iter()
+            .fold(*point, |fastest, &other| if other.freq_ghz > fastest.freq_ghz { other } else { fastest });
+        
+        let frequency_ratio = point.freq_ghz / nominal.freq_ghz;
+        let voltage_ratio = point.voltage / nominal.voltage;
+        let mut curve = self.demand_curve(self.precision_factors(precision));
+        curve.computation_factor *= voltage_ratio * voltage_ratio * frequency_ratio;
+        curve.inference_time /= frequency_ratio;
+        Ok(self.apply_thermal_limit(curve.demand(batch_size.get() as f64)).total_energy)
+    }
+    
+    // Most efficient DVFS point at a batch size as (point_index, samples per joule);
+    // ties go to the earlier point, and None when the model lists no points
+    pub fn optimize_dvfs(&self, batch_size: u32, precision: Precision) -> Result<Option<(usize, f64)>, OptimizerError> {
+        BatchSize::try_from(batch_size)?;
+        let mut optimum: Option<(usize, f64)> = None;
+        for point_index in 0..self.model_params.dvfs_points.len() {
+            let efficiency = batch_size as f64 / self.energy_consumption_at_dvfs(batch_size, precision, point_index)?;
+            if optimum.is_none_or(|(_, best)| efficiency > best) {
+                optimum = Some((point_index, efficiency));
+            }
+        }
+        Ok(optimum)
+    }
+    
+    // Static (leakage) power in watts at a temperature (°C): base_power_consumption at
+    // the 25°C reference, scaled by the Arrhenius factor relative to that reference.
+    // Temperatures at or below absolute zero are InvalidTemperature.
+    pub fn leakage_power(&self, temperature_c: f64) -> Result<f64, OptimizerError> {
+        Ok(self.model_params.base_power_consumption * leakage_scale(temperature_c)?)
+    }
+    
+    // Energy at an ambient temperature (°C). Base power includes static leakage, which
+    // grows with temperature as in leakage_power, relative to the 25°C reference used
+    // by energy_consumption. Near the thermal ceiling the extra leakage pushes total
+    // power into the TDP cap and the batch starts to throttle.
+    pub fn energy_consumption_at_temp(&self, batch_size: u32, precision: Precision, ambient_c: f64) -> Result<f64, OptimizerError> {
+        let demand = self.power_demand(BatchSize::try_from(batch_size)?, precision);
+        let leakage_scale = leakage_scale(ambient_c)?;
+        let heated = EnergyBreakdown::new(
+            demand.base_power * leakage_scale,
+            demand.computation_power,
+            demand.memory_power,
+            demand.inference_time,
+        );
+        Ok(self.apply_thermal_limit(heated).total_energy)
+    }
+    
+    // Cap the demanded power at TDP, stretching inference time by the same ratio
+    fn apply_thermal_limit(&self, demand: EnergyBreakdown) -> EnergyBreakdown {
+        let demanded_power = demand.total_power();
+        if demanded_power <= self.model_params.thermal_design_power {
+            return demand;
+        }
+        
+        let throttle = self.model_params.thermal_design_power / demanded_power;
+        EnergyBreakdown::new(
+            demand.base_power * throttle,
+            demand.computation_power * throttle,
+            demand.memory_power * throttle,
+            demand.inference_time / throttle,
+        )
+    }
+    
+    // Energy (joules) for a model whose layers run at different precisions. Each entry
+    // is a precision and the fraction of compute it covers; the fractions must sum to 1.
+    // The compute and memory-bandwidth factors are blended by those fractions.
+    pub fn energy_consumption_mixed(&self, batch_size: u32, precisions: &[(Precision, f64)]) -> Result<f64, OptimizerError> {
+        let total_fraction: f64 = precisions.iter().map(|&(_, fraction)| fraction).sum();
+        if precisions.iter().any(|&(_, fraction)| fraction.is_nan() || fraction < 0.0)
+            || (total_fraction - 1.0).abs() > PRECISION_MIX_TOLERANCE {
+            return Err(OptimizerError::InvalidPrecisionMix);
+        }
+        
+        let factors = precisions.iter().fold((0.0, 0.0), |(compute, memory), &(precision, fraction)| {
+            let (precision_factor, memory_bandwidth_factor) = self.precision_factors(precision);
+            (compute + fraction * precision_factor, memory + fraction * memory_bandwidth_factor)
+        });
+        Ok(self.apply_thermal_limit(self.power_demand_with_factors(BatchSize::try_from(batch_size)?, factors)).total_energy)
+    }
+    
+    // Energy (joules) of one batch through a network of heterogeneous layers. The
+    // device draws the power of the whole network (compute factors and memory summed
+    // over all layers) at each layer's precision; each layer is charged the share of
+    // compute and base energy its compute factor is of the total, and the share of
+    // memory energy its memory_usage is. Base power is thus counted once across the
+    // pass, one layer with the model-wide factors matches energy_consumption, and
+    // splitting a layer in two leaves the total unchanged. Thermal throttling doesn't
+    // change batch energy, so it is not applied here.
+    pub fn layered_energy(&self, batch_size: u32, layers: &[LayerSpec]) -> Result<f64, OptimizerError> {
+        let batch_size = BatchSize::try_from(batch_size)?;
+        let total_compute: f64 = layers.iter().map(|layer| layer.compute_factor).sum();
+        let total_memory: f64 = layers.iter().map(|layer| layer.memory_usage).sum();
+        if layers.iter().any(|layer| !(layer.compute_factor >= 0.0 && layer.memory_usage >= 0.0))
+            || !(total_compute > 0.0 && total_compute.is_finite() && total_memory.is_finite()) {
+            return Err(OptimizerError::InvalidLayerSpec);
+        }
+        
+        let mut total_energy = 0.0;
+        for layer in layers {
+            let mut curve = self.demand_curve(self.precision_factors(layer.precision));
+            curve.computation_factor = total_compute;
+            curve.memory_power_scale = total_memory * self.model_params.memory_power_factor;
+            let demand = curve.demand(batch_size.get() as f64);
+            
+            let compute_share = layer.compute_factor / total_compute;
+            let memory_share = if total_memory > 0.0 { layer.memory_usage / total_memory } else { 0.0 };
+            total_energy += ((demand.base_power + demand.computation_power) * compute_share
+                + demand.memory_power * memory_share) * demand.inference_time;
+        }
+        
+        Ok(total_energy)
+    }
+    
+    // energy_consumption at every batch size from min_batch to max_batch, in order.
+    // The precision lookup and every batch-independent term are evaluated once, leaving
+    // only the per-batch demand and TDP cap in the loop; results match
+    // energy_consumption exactly.
+    pub fn energy_range(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<Vec<f64>, OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        let min_batch = BatchSize::try_from(min_batch)?.get();
+        
+        let curve = self.demand_curve(self.precision_factors(precision));
+        Ok((min_batch..=max_batch)
+            .map(|batch_size| self.apply_thermal_limit(curve.demand(batch_size as f64)).total_energy)
+            .collect())
+    }
+    
+    // Power components and inference time the batch demands before the TDP cap
+    fn power_demand(&self, batch_size: BatchSize, precision: Precision) -> EnergyBreakdown {
+        self.power_demand_with_factors(batch_size, self.precision_factors(precision))
+    }
+    
+    // power_demand for explicit (compute, memory bandwidth) precision factors
+    fn power_demand_with_factors(&self, batch_size: BatchSize, factors: (f64, f64)) -> EnergyBreakdown {
+        self.demand_curve(factors).demand(batch_size.get() as f64)
+    }
+    
+    // Everything in the power model that doesn't depend on batch size, evaluated once
+    fn demand_curve(&self, (precision_factor, memory_bandwidth_factor): (f64, f64)) -> DemandCurve {
+        let params = &self.model_params;
+        DemandCurve {
+            precision_factor,
+            memory_bandwidth_factor,
+            // Memory bandwidth saturation (realistic memory bottleneck); faster memory
+            // pushes the knee to larger batches, growing with the square root of the
+            // bandwidth ratio so 256 GB/s keeps the original knee of batch 32
+            saturation_knee: self.saturation_knee(),
+            // Structured sparsity reduces the work and the bytes moved, sublinearly
+            sparsity_scale: (1.0 - params.sparsity).powf(SPARSITY_EXPONENT),
+            // More TDP headroom delays the onset of thermal throttling
+            thermal_onset: params.thermal.onset_batch * (params.thermal_design_power / REFERENCE_THERMAL_DESIGN_POWER),
+            max_thermal_penalty: params.thermal.max_penalty,
+            computation_factor: params.computation_factor,
+            memory_power_scale: params.memory_usage * params.memory_power_factor,
+            base_power_consumption: params.base_power_consumption,
+            // Cache misses increase as the working set outgrows the cache:
+            //   cache_efficiency = 1 - P * (1 - exp(-batch / (P * B * cache_size / C)))
+            // with P = MAX_CACHE_PENALTY, B = CACHE_KNEE_BATCH, C = REFERENCE_CACHE_SIZE.
+            // The penalty starts with slope 1/B at a C-sized cache and levels off smoothly at P;
+            // a larger cache stretches the curve so bigger batches still fit.
+            cache_knee: MAX_CACHE_PENALTY * CACHE_KNEE_BATCH * params.cache_size / REFERENCE_CACHE_SIZE,
+            inference_time: params.inference_time,
+            cache_penalty: 1.0,
+        }
+    }
+    
+    // Batch size at which memory bandwidth reaches a fraction of saturation, inverting
+    // memory_saturation = 1 - exp(-batch / knee): batch = -knee * ln(1 - fraction).
+    // Fractional, since the curve is continuous; the knee is the same for every precision.
+    pub fn saturation_batch(&self, fraction: f64) -> Result<f64, OptimizerError> {
+        if !(fraction > 0.0 && fraction < 1.0) {
+            return Err(OptimizerError::InvalidSaturationFraction);
+        }
+        Ok(-self.saturation_knee() * (1.0 - fraction).ln())
+    }
+    
+    // Batch size scale of memory_saturation for this memory bandwidth
+    fn saturation_knee(&self) -> f64 {
+        SATURATION_KNEE_BATCH * (self.model_params.memory_bandwidth / REFERENCE_MEMORY_BANDWIDTH).sqrt()
+    }
+    
+    // energy_consumption bound to one precision, for generic numerical code (root
+    // finders, plotters) that only knows Fn(u32) -> f64. Batch size 0 yields NaN.
+    pub fn energy_fn(&self, precision: Precision) -> impl Fn(u32) -> f64 + '_ {
+        move |batch_size| self.energy_consumption(batch_size, precision).unwrap_or(f64::NAN)
+    }
+    
+    // efficiency bound to one precision, like energy_fn. Batch size 0 yields NaN.
+    pub fn efficiency_fn(&self, precision: Precision) -> impl Fn(u32) -> f64 + '_ {
+        move |batch_size| self.efficiency(batch_size, precision).unwrap_or(f64::NAN)
+    }
+    
+    // Energy per sample (joules) when the model's one-time startup_energy is spread over
+    // total_samples served at this batch size. Converges to the steady-state
+    // energy_consumption / batch_size as total_samples grows.
+    pub fn amortized_energy_per_sample(&self, batch_size: u32, precision: Precision, total_samples: u32) -> Result<f64, OptimizerError> {
+        if total_samples == 0 {
+            return Err(OptimizerError::ZeroSampleCount);
+        }
+        let steady_state = self.energy_consumption(batch_size, precision)? / batch_size as f64;
+        Ok(steady_state + self.model_params.startup_energy / total_samples as f64)
+    }
+    
+    // Power (watts) drawn while idle: base power at zero utilization
+    fn idle_power(&self) -> f64 {
+        self.model_params.base_power_consumption * 0.8
+    }
+    
+    // Steady-state serving with dynamic batching: a batch is dispatched once max_batch
+    // requests wait or max_wait_ms after its first request, whichever comes first.
+    // At qps arrivals per second the window collects 1 + qps * wait requests; if that
+    // batch can't keep up with arrivals, queued requests grow it until it does. Each
+    // request waits on average half the batch's fill time, then the batch's compute
+    // time, and the accelerator idles between batches.
+    pub fn serve_simulation(&self, qps: f64, max_batch: u32, max_wait_ms: f64, precision: Precision) -> Result<ServeStats, OptimizerError> {
+        if !(qps > 0.0 && qps.is_finite() && max_wait_ms >= 0.0) {
+            return Err(OptimizerError::InvalidServingLoad);
+        }
+        let max_batch = BatchSize::try_from(max_batch)?.get();
+        let max_wait = max_wait_ms / 1000.0;
+        
+        let window_batch = (1.0 + qps * max_wait).round().min(max_batch as f64) as u32;
+        let mut batch_size = window_batch.max(1);
+        while batch_size < max_batch && self.throughput(batch_size, precision)? < qps {
+            batch_size += 1;
+        }
+        let breakdown = self.energy_breakdown(batch_size, precision)?;
+        let batch_interval = batch_size as f64 / qps;
+        if breakdown.inference_time > batch_interval {
+            return Err(OptimizerError::Overloaded);
+        }
+        
+        let fill_time = ((batch_size - 1) as f64 / qps).min(max_wait.max(breakdown.inference_time));
+        let idle_energy = self.idle_power() * (batch_interval - breakdown.inference_time);
+        Ok(ServeStats {
+            batch_size,
+            average_latency: fill_time / 2.0 + breakdown.inference_time,
+            energy_per_request: (breakdown.total_energy + idle_energy) / batch_size as f64,
+            utilization: breakdown.inference_time / batch_interval,
+        })
+    }
+    
+    // Fleet that serves target_qps with the least total power. For each batch size up to
+    // max_batch the fleet runs the fewest replicas whose throughput covers the load, and
+    // the batch must fill from requests arriving while the previous batch computes
+    // (batch_size <= 1 + per-replica qps * latency) rather than by holding requests
+    // back, so light loads run at batch 1 and loads beyond one replica scale out.
+    // Replicas idle between batches, as in serve_simulation. Ties go to the smaller batch.
+    // Unlike optimal_replicas, which assumes saturated replicas and so settles on the
+    // most efficient batch whatever the load, this prices the power actually drawn at
+    // target_qps, including idle gaps and the latency of waiting for a batch to fill.
+    pub fn recommend_fleet(&self, target_qps: f64, precision: Precision, max_batch: u32) -> Result<FleetPlan, OptimizerError> {
+        if !(target_qps > 0.0 && target_qps.is_finite()) {
+            return Err(OptimizerError::InvalidServingLoad);
+        }
+        let max_batch = BatchSize::try_from(max_batch)?.get();
+        check_scan_range(1, max_batch)?;
+        
+        let mut best: Option<FleetPlan> = None;
+        for batch_size in 1..=max_batch {
+            let breakdown = self.energy_breakdown(batch_size, precision)?;
+            let capacity = batch_size as f64 / breakdown.inference_time;
+            let replicas = (target_qps / capacity).ceil();
+            if replicas > u32::MAX as f64 {
+                continue;
+            }
+            let replica_qps = target_qps / replicas;
+            if batch_size as f64 > 1.0 + replica_qps * breakdown.inference_time {
+                continue;
+            }
+            
+            let batch_interval = batch_size as f64 / replica_qps;
+            let idle_energy = self.idle_power() * (batch_interval - breakdown.inference_time).max(0.0);
+            let energy_per_request = (breakdown.total_energy + idle_energy) / batch_size as f64;
+            let total_power = energy_per_request * target_qps;
+            if best.is_none_or(|plan| total_power < plan.total_power) {
+                best = Some(FleetPlan { replicas: replicas as u32, batch_size, total_power, energy_per_request });
+            }
+        }
+        
+        best.ok_or(OptimizerError::Overloaded)
+    }
+    
+    // Total energy (joules) over a serving window of window_seconds that handles
+    // `requests` requests in batches of batch_size (the last batch takes the remainder).
+    // The accelerator sits at idle power for whatever time the batches leave over.
+    pub fn window_energy(&self, requests: u32, batch_size: u32, precision: Precision, window_seconds: f64) -> Result<f64, OptimizerError> {
+        let batch_size = BatchSize::try_from(batch_size)?.get();
+        
+        let full_batch = self.energy_breakdown(batch_size, precision)?;
+        let full_batches = (requests / batch_size) as f64;
+        let mut active_energy = full_batches * full_batch.total_energy;
+        let mut active_time = full_batches * full_batch.inference_time;
+        
+        let remainder = requests % batch_size;
+        if remainder > 0 {
+            let last_batch = self.energy_breakdown(remainder, precision)?;
+            active_energy += last_batch.total_energy;
+            active_time += last_batch.inference_time;
+        }
+        
+        if active_time > window_seconds {
+            return Err(OptimizerError::WindowTooShort);
+        }
+        Ok(active_energy + self.idle_power() * (window_seconds - active_time))
+    }
+    
+    // Instantaneous power as (time_seconds, watts) samples while serving num_batches
+    // back to back. Starts at idle power, steps up to the active draw at t = 0, holds it
+    // at every batch boundary, and drops back to idle once the last batch finishes.
+    pub fn power_timeline(&self, batch_size: u32, precision: Precision, num_batches: u32) -> Result<Vec<(f64, f64)>, OptimizerError> {
+        let breakdown = self.energy_breakdown(batch_size, precision)?;
+        let active_power = breakdown.total_power();
+        let idle_power = self.idle_power();
+        
+        let mut timeline = vec![(0.0, idle_power)];
+        if num_batches == 0 {
+            return Ok(timeline);
+        }
+        for batch in 0..=num_batches {
+            timeline.push((batch as f64 * breakdown.inference_time, active_power));
+        }
+        timeline.push((num_batches as f64 * breakdown.inference_time, idle_power));
+        
+        Ok(timeline)
+    }
+    
+    // Calculate efficiency (samples processed per joule)
+    pub fn efficiency(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        let energy = self.energy_consumption(batch_size, precision)?;
+        Ok((batch_size as f64) / energy)
+    }
+    
+    // Tera-operations per second per watt, the figure vendors quote on datasheets:
+    // ops_per_inference * batch_size / inference_time / power, which is ops per
+    // joule of the batch scaled to tera
+    pub fn tops_per_watt(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        let breakdown = self.energy_breakdown(batch_size, precision)?;
+        let ops_per_second = self.model_params.ops_per_inference * batch_size as f64 / breakdown.inference_time;
+        Ok(ops_per_second / breakdown.total_power() / 1e12)
+    }
+    
+    // Real samples per joule when only actual_samples of the batch's slots carry
+    // requests and the rest are padding; equals efficiency for a full batch
+    pub fn effective_efficiency(&self, batch_size: u32, actual_samples: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        if actual_samples > batch_size {
+            return Err(OptimizerError::SamplesExceedBatch);
+        }
+        let energy = self.energy_consumption(batch_size, precision)?;
+        Ok(actual_samples as f64 / energy)
+    }
+    
+    // Extra energy (joules) of growing the batch by one sample, as the forward
+    // difference energy(b + 1) - energy(b). At u32::MAX, where b + 1 does not exist,
+    // the backward difference energy(b) - energy(b - 1) is used instead.
+    pub fn marginal_energy(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        forward_difference(batch_size, |batch_size| self.energy_consumption(batch_size, precision))
+    }
+    
+    // Change in efficiency (samples/J) from growing the batch by one sample; positive
+    // while admitting another request still improves efficiency
+    pub fn marginal_efficiency(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        forward_difference(batch_size, |batch_size| self.efficiency(batch_size, precision))
+    }
+    
+    // Energy, per-sample energy, efficiency, latency, and power at one operating point
+    pub fn estimate(&self, batch_size: u32, precision: Precision) -> Result<EstimateSummary, OptimizerError> {
+        let breakdown = self.energy_breakdown(batch_size, precision)?;
+        Ok(EstimateSummary {
+            batch_size,
+            precision,
+            energy: breakdown.total_energy,
+            energy_per_sample: breakdown.total_energy / batch_size as f64,
+            efficiency: batch_size as f64 / breakdown.total_energy,
+            latency: breakdown.inference_time,
+            power: breakdown.total_power(),
+        })
+    }
+    
+    // Energy, efficiency, throughput, and latency of each precision at one batch size
+    pub fn precision_table(&self, batch_size: u32, precisions: &[Precision]) -> Result<PrecisionTable, OptimizerError> {
+        let rows = precisions
+            .iter()
+            .map(|&precision| {
+                let breakdown = self.energy_breakdown(batch_size, precision)?;
+                Ok(PrecisionRow {
+                    precision,
+                    energy: breakdown.total_energy,
+                    efficiency: batch_size as f64 / breakdown.total_energy,
+                    throughput: batch_size as f64 / breakdown.inference_time,
+                    latency: breakdown.inference_time,
+                })
+            })
+            .collect::<Result<Vec<_>, OptimizerError>>()?;
+        Ok(PrecisionTable { batch_size, rows })
+    }
+    
+    // Finite-difference elasticity of energy_consumption with respect to every
+    // ModelParams field it depends on, each perturbed by SENSITIVITY_STEP relative to
+    // its value (central difference). Fields at zero have zero elasticity.
+    pub fn sensitivity(&self, batch_size: u32, precision: Precision) -> Result<SensitivityReport, OptimizerError> {
+        let fields: [(&'static str, FieldAccessor); 11] = [
+            ("base_power_consumption", |params| &mut params.base_power_consumption),
+            ("computation_factor", |params| &mut params.computation_factor),
+            ("memory_usage", |params| &mut params.memory_usage),
+            ("memory_power_factor", |params| &mut params.memory_power_factor),
+            ("inference_time", |params| &mut params.inference_time),
+            ("thermal_design_power", |params| &mut params.thermal_design_power),
+            ("cache_size", |params| &mut params.cache_size),
+            ("memory_bandwidth", |params| &mut params.memory_bandwidth),
+            ("sparsity", |params| &mut params.sparsity),
+            ("thermal.onset_batch", |params| &mut params.thermal.onset_batch),
+            ("thermal.max_penalty", |params| &mut params.thermal.max_penalty),
+        ];
+        let energy = self.energy_consumption(batch_size, precision)?;
+        
+        let mut perturbed = self.clone();
+        let mut sensitivities = Vec::with_capacity(fields.len());
+        for (field, value_of) in fields {
+            let value = *value_of(&mut perturbed.model_params);
+            if value == 0.0 {
+                sensitivities.push((field, 0.0));
+                continue;
+            }
+            let step = value * SENSITIVITY_STEP;
+            *value_of(&mut perturbed.model_params) = value + step;
+            let above = perturbed.energy_consumption(batch_size, precision)?;
+            *value_of(&mut perturbed.model_params) = value - step;
+            let below = perturbed.energy_consumption(batch_size, precision)?;
+            *value_of(&mut perturbed.model_params) = value;
+            
+            sensitivities.push((field, (above - below) / (2.0 * SENSITIVITY_STEP * energy)));
+        }
+        sensitivities.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+        
+        Ok(SensitivityReport { energy, sensitivities })
+    }
+    
+    // How much energy quantizing (or otherwise switching) from one precision to
+    // another saves at this batch size; regressions show up as negative savings
+    pub fn quantization_savings(&self, batch_size: u32, from: Precision, to: Precision) -> Result<SavingsReport, OptimizerError> {
+        let from_energy = self.energy_consumption(batch_size, from)?;
+        let to_energy = self.energy_consumption(batch_size, to)?;
+        let joules_saved = from_energy - to_energy;
+        Ok(SavingsReport {
+            joules_saved,
+            percent_saved: joules_saved / from_energy * 100.0,
+            efficiency_ratio: from_energy / to_energy,
+        })
+    }
+    
+    // A/B comparison of two batch sizes by efficiency at one precision
+    pub fn compare_batches(&self, a: u32, b: u32, precision: Precision) -> Result<BatchComparison, OptimizerError> {
+        let efficiency_a = self.efficiency(a, precision)?;
+        let efficiency_b = self.efficiency(b, precision)?;
+        let relative_difference = (efficiency_a - efficiency_b) / efficiency_b;
+        let preferred = if relative_difference.abs() <= EFFICIENCY_TOLERANCE {
+            Preference::Equal
+        } else if relative_difference > 0.0 {
+            Preference::A
+        } else {
+            Preference::B
+        };
+        Ok(BatchComparison { preferred, efficiency_a, efficiency_b, percent_difference: relative_difference * 100.0 })
+    }
+    
+    // Samples per second at this batch size, including any thermal throttling slowdown
+    pub fn throughput(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        Ok(batch_size as f64 / self.latency(batch_size, precision)?)
+    }
+    
+    // Seconds to run one batch, including any thermal throttling slowdown
+    pub fn latency(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        Ok(self.energy_breakdown(batch_size, precision)?.inference_time)
+    }
+    
+    // Energy-delay product (joule-seconds) of one batch: energy times inference time
+    pub fn energy_delay_product(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        let breakdown = self.energy_breakdown(batch_size, precision)?;
+        Ok(breakdown.total_energy * breakdown.inference_time)
+    }
+    
+    // energy_delay_product divided by batch_size squared: energy per sample times
+    // seconds per sample. Both batch terms grow with the batch, so only this normalized
+    // form compares batch sizes meaningfully
+    pub fn energy_delay_product_per_sample(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        let samples = batch_size as f64;
+        Ok(self.energy_delay_product(batch_size, precision)? / (samples * samples))
+    }
+    
+    // Power demanded and actually drawn (watts), to detect when throttling kicks in
+    pub fn power_draw(&self, batch_size: u32, precision: Precision) -> Result<PowerDraw, OptimizerError> {
+        let unclamped_power = self.power_demand(BatchSize::try_from(batch_size)?, precision).total_power();
+        let clamped_power = self.energy_breakdown(batch_size, precision)?.total_power();
+        Ok(PowerDraw { unclamped_power, clamped_power })
+    }
+    
+    // Remaining thermal budget (watts) at a batch size, negative when the demand is clamped
+    pub fn power_headroom(&self, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        let total_power = self.power_demand(BatchSize::try_from(batch_size)?, precision).total_power();
+        Ok(self.model_params.thermal_design_power - total_power)
+    }
+    
+    // Check whether sustained operation stays below the throttle threshold of TDP,
+    // i.e. the operating point can run indefinitely without thermal throttling
+    pub fn is_thermally_stable(&self, batch_size: u32, precision: Precision) -> Result<bool, OptimizerError> {
+        let total_power = self.power_demand(BatchSize::try_from(batch_size)?, precision).total_power();
+        Ok(total_power < THERMAL_THROTTLE_THRESHOLD * self.model_params.thermal_design_power)
+    }
+    
+    // Roofline classification of an operating point, with the crossover batch size at
+    // which it turns compute-bound. Each batch streams the weights once, taking
+    // memory_usage * memory factor / memory_bandwidth seconds, while compute takes
+    // inference_time * computation_factor * compute factor per sample, so arithmetic
+    // intensity grows with batch size and batches below the crossover are memory-bound.
+    pub fn operating_point(&self, batch_size: u32, precision: Precision) -> Result<(Bound, u32), OptimizerError> {
+        let batch_size = BatchSize::try_from(batch_size)?.get();
+        let (precision_factor, memory_bandwidth_factor) = self.precision_factors(precision);
+        let memory_time = self.model_params.memory_usage * memory_bandwidth_factor / self.model_params.memory_bandwidth;
+        let compute_time_per_sample = self.model_params.inference_time * self.model_params.computation_factor * precision_factor;
+        
+        let crossover_batch = ((memory_time / compute_time_per_sample).ceil() as u32).max(1);
+        let bound = if batch_size < crossover_batch { Bound::Memory } else { Bound::Compute };
+        Ok((bound, crossover_batch))
+    }
+    
+    // Thermal headroom at every batch size in the range
+    pub fn power_headroom_sweep(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<Vec<(u32, f64)>, OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        (min_batch..=max_batch)
+            .map(|batch_size| Ok((batch_size, self.power_headroom(batch_size, precision)?)))
+            .collect()
+    }
+    
+    // Find optimal batch size for energy efficiency by scanning every batch in the
+    // range; ranges over MAX_SCAN_BATCHES long return RangeTooLarge and inverted ones
+    // (min_batch > max_batch) EmptyRange. With the `tracing` feature each candidate is
+    // logged as a debug event alongside the best batch so far.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn optimize_batch_size(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<(u32, f64), OptimizerError> {
+        let mut optimum: Option<(u32, f64)> = None;
+        
+        for point in self.efficiency_iter(precision, min_batch, max_batch)? {
+            let (batch_size, efficiency) = point?;
+            let best = match optimum {
+                Some(best) if best.1 >= efficiency => best,
+                _ => (batch_size, efficiency),
+            };
+            optimum = Some(best);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(batch_size, efficiency, best_batch = best.0, best_efficiency = best.1, "evaluated batch size");
+        }
+        
+        Ok(optimum.expect("efficiency_iter checked the range is non-empty"))
+    }
+    
+    // Most efficient (batch_size, precision, efficiency) over every batch size in range
+    // and every listed precision, evaluating each pair once. Ties go to the smaller
+    // batch, then to the precision listed first; None when precisions is empty.
+    pub fn optimize_global(&self, min_batch: u32, max_batch: u32, precisions: &[Precision]) -> Result<Option<(u32, Precision, f64)>, OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        let mut optimum: Option<(u32, Precision, f64)> = None;
+        
+        for batch_size in min_batch..=max_batch {
+            for &precision in precisions {
+                let efficiency = self.efficiency(batch_size, precision)?;
+                if optimum.is_none_or(|(_, _, best)| efficiency > best) {
+                    optimum = Some((batch_size, precision, efficiency));
+                }
+            }
+        }
+        
+        Ok(optimum)
+    }
+    
+    // Most efficient (batch_size, precision, efficiency) over every precision whose
+    // accuracy_delta is at most max_accuracy_drop percentage points; precisions without
+    // an accuracy_delta entry count as lossless. None when no precision qualifies.
+    pub fn optimize_accuracy_constrained(&self, min_batch: u32, max_batch: u32, max_accuracy_drop: f64) -> Result<Option<(u32, Precision, f64)>, OptimizerError> {
+        if max_accuracy_drop.is_nan() || max_accuracy_drop < 0.0 {
+            return Err(OptimizerError::InvalidAccuracyDrop);
+        }
+        let mut optimum: Option<(u32, Precision, f64)> = None;
+        
+        for precision in Precision::ALL {
+            let accuracy_drop = self.model_params.accuracy_delta.get(&precision).copied().unwrap_or(0.0);
+            if accuracy_drop > max_accuracy_drop {
+                continue;
+            }
+            let (batch_size, efficiency) = self.optimize_batch_size(precision, min_batch, max_batch)?;
+            if optimum.is_none_or(|(_, _, best)| efficiency > best) {
+                optimum = Some((batch_size, precision, efficiency));
+            }
+        }
+        
+        Ok(optimum)
+    }
+    
+    // Find the batch size with the lowest total energy for one call, rather than the
+    // best samples per joule. With the built-in precision factors and typical parameters
+    // every power term and the inference time grow with batch size, so this is
+    // min_batch. A memory factor near 2 on a low-bandwidth chip lets memory saturation
+    // cut inference time faster than the batch grows, and with high base power the
+    // minimum then moves to a larger batch. Ties go to the smaller batch.
+    pub fn min_energy_batch(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<(u32, f64), OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        let mut optimum = (min_batch, self.energy_consumption(min_batch, precision)?);
+        
+        for batch_size in min_batch.saturating_add(1)..=max_batch {
+            let energy = self.energy_consumption(batch_size, precision)?;
+            if energy < optimum.1 {
+                optimum = (batch_size, energy);
+            }
+        }
+        
+        Ok(optimum)
+    }
+    
+    // Find the batch size with the highest throughput (samples/s). Throughput keeps
+    // rising with batch size until the TDP cap stretches inference time, so this
+    // is usually larger than the energy-optimal batch. Ties go to the smaller batch.
+    pub fn optimize_throughput(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<(u32, f64), OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        let mut optimum = (min_batch, self.throughput(min_batch, precision)?);
+        
+        for batch_size in min_batch.saturating_add(1)..=max_batch {
+            let throughput = self.throughput(batch_size, precision)?;
+            if throughput > optimum.1 {
+                optimum = (batch_size, throughput);
+            }
+        }
+        
+        Ok(optimum)
+    }
+    
+    // Find the batch size with the lowest energy_delay_product_per_sample (the batch
+    // EDP always favors the smallest batch). Weighing energy and delay equally, it
+    // typically lands between the energy-optimal batch of optimize_batch_size and the
+    // throughput-optimal one. Ties go to the smaller batch.
+    pub fn optimize_edp(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<(u32, f64), OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        let mut optimum = (min_batch, self.energy_delay_product_per_sample(min_batch, precision)?);
+        
+        for batch_size in min_batch.saturating_add(1)..=max_batch {
+            let edp = self.energy_delay_product_per_sample(batch_size, precision)?;
+            if edp < optimum.1 {
+                optimum = (batch_size, edp);
+            }
+        }
+        
+        Ok(optimum)
+    }
+    
+    // Find the batch size maximizing
+    //   energy_weight * efficiency' + (1 - energy_weight) * throughput'
+    // where efficiency' and throughput' are min-max normalized to [0, 1] over the range.
+    // Returns the batch and its blended score. A weight of 1 picks the same batch as
+    // optimize_batch_size and 0 the same as optimize_throughput; ties go to the smaller batch.
+    pub fn optimize_weighted(&self, precision: Precision, min_batch: u32, max_batch: u32, energy_weight: f64) -> Result<(u32, f64), OptimizerError> {
+        if !(0.0..=1.0).contains(&energy_weight) {
+            return Err(OptimizerError::InvalidEnergyWeight);
+        }
+        check_scan_range(min_batch, max_batch)?;
+        
+        let points = (min_batch..=max_batch)
+            .map(|batch_size| Ok((batch_size, self.efficiency(batch_size, precision)?, self.throughput(batch_size, precision)?)))
+            .collect::<Result<Vec<_>, OptimizerError>>()?;
+        
+        let efficiency_range = min_max(points.iter().map(|point| point.1));
+        let throughput_range = min_max(points.iter().map(|point| point.2));
+        
+        let mut optimum: Option<(u32, f64)> = None;
+        for &(batch_size, efficiency, throughput) in &points {
+            let score = energy_weight * normalize(efficiency, efficiency_range)
+                + (1.0 - energy_weight) * normalize(throughput, throughput_range);
+            if optimum.is_none_or(|(_, best)| score > best) {
+                optimum = Some((batch_size, score));
+            }
+        }
+        
+        Ok(optimum.expect("points holds at least one batch size"))
+    }
+    
+    // Find the most efficient batch whose inference time stays within max_latency_s,
+    // or None when even the fastest batch in the range is too slow
+    pub fn optimize_under_latency(&self, precision: Precision, min_batch: u32, max_batch: u32, max_latency_s: f64) -> Result<Option<(u32, f64)>, OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        let mut optimum: Option<(u32, f64)> = None;
+        
+        for batch_size in min_batch..=max_batch {
+            let breakdown = self.energy_breakdown(batch_size, precision)?;
+            if breakdown.inference_time > max_latency_s {
+                continue;
+            }
+            let efficiency = batch_size as f64 / breakdown.total_energy;
+            if optimum.is_none_or(|(_, best)| efficiency > best) {
+                optimum = Some((batch_size, efficiency));
+            }
+        }
+        
+        Ok(optimum)
+    }
+    
+    // Batches on the (latency, energy per sample) Pareto frontier: no other batch in the
+    // range is both faster and cheaper per sample. Sorted by increasing latency.
+    pub fn pareto_frontier(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<Vec<(u32, f64, f64)>, OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        let mut points = Vec::new();
+        for batch_size in min_batch..=max_batch {
+            let breakdown = self.energy_breakdown(batch_size, precision)?;
+            points.push((batch_size, breakdown.inference_time, breakdown.total_energy / batch_size as f64));
+        }
+        
+        // Sweep from fastest to slowest; a point survives only if it is cheaper per
+        // sample than every faster point
+        points.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.2.total_cmp(&b.2)));
+        let mut frontier: Vec<(u32, f64, f64)> = Vec::new();
+        for point in points {
+            if frontier.last().is_none_or(|&(_, _, best_energy)| point.2 < best_energy) {
+                frontier.push(point);
+            }
+        }
+        
+        Ok(frontier)
+    }
+    
+    // Lazily yield (batch_size, energy) across the range without allocating; an item is
+    // an error only for batch size 0. The range is checked up front like the scans.
+    pub fn energy_iter(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<impl Iterator<Item = Result<(u32, f64), OptimizerError>> + '_, OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        Ok((min_batch..=max_batch).map(move |batch_size| Ok((batch_size, self.energy_consumption(batch_size, precision)?))))
+    }
+    
+    // Lazily yield (batch_size, efficiency) like energy_iter; efficiency_curve collects it
+    // and optimize_batch_size folds over it, so both evaluate the range the same way
+    pub fn efficiency_iter(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<impl Iterator<Item = Result<(u32, f64), OptimizerError>> + '_, OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        Ok((min_batch..=max_batch).map(move |batch_size| Ok((batch_size, self.efficiency(batch_size, precision)?))))
+    }
+    
+    // Peak of the efficiency curve and the contiguous batch range around it that stays
+    // within PLATEAU_FRACTION (95%) of the peak efficiency
+    pub fn efficiency_profile(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<ProfileStats, OptimizerError> {
+        let curve = self.efficiency_curve(precision, min_batch, max_batch)?;
+        
+        let mut peak = 0;
+        for (index, &(_, efficiency)) in curve.iter().enumerate() {
+            if efficiency > curve[peak].1 {
+                peak = index;
+            }
+        }
+        let threshold = PLATEAU_FRACTION * curve[peak].1;
+        let on_plateau = |index: &usize| curve[*index].1 >= threshold;
+        let first = (0..peak).rev().take_while(on_plateau).last().unwrap_or(peak);
+        let last = (peak + 1..curve.len()).take_while(on_plateau).last().unwrap_or(peak);
+        
+        Ok(ProfileStats {
+            optimal_batch: curve[peak].0,
+            peak_efficiency: curve[peak].1,
+            plateau_min: curve[first].0,
+            plateau_max: curve[last].0,
+            plateau_width: curve[last].0 - curve[first].0 + 1,
+        })
+    }
+    
+    // Mean, median, 95th percentile, and spread of efficiency_curve, weighting every
+    // batch size in the range equally
+    pub fn efficiency_stats(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<EfficiencyStats, OptimizerError> {
+        let mut values: Vec<f64> = self
+            .efficiency_curve(precision, min_batch, max_batch)?
+            .into_iter()
+            .map(|(_, efficiency)| efficiency)
+            .collect();
+        values.sort_by(f64::total_cmp);
+        
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let variance = values.iter().map(|value| (value - mean) * (value - mean)).sum::<f64>() / count;
+        Ok(EfficiencyStats {
+            mean,
+            median: percentile(&values, 0.5),
+            p95: percentile(&values, 0.95),
+            std_dev: variance.sqrt(),
+        })
+    }
+    
+    // Efficiency at every batch size in the range, as (batch_size, efficiency) pairs
+    pub fn efficiency_curve(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<Vec<(u32, f64)>, OptimizerError> {
+        self.efficiency_iter(precision, min_batch, max_batch)?.collect()
+    }
+    
+    // Same result as optimize_batch_size, evaluating the range on the rayon thread pool.
+    // Ties go to the smaller batch size, matching the serial scan.
+    #[cfg(feature = "rayon")]
+    pub fn optimize_batch_size_parallel(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<(u32, f64), OptimizerError> {
+        check_scan_range(min_batch, max_batch)?;
+        let optimum = (min_batch..=max_batch)
+            .into_par_iter()
+            .map(|batch_size| Ok((batch_size, self.efficiency(batch_size, precision)?)))
+            .try_reduce_with(|a: (u32, f64), b: (u32, f64)| {
+                let b_wins = b.1 > a.1 || (b.1 == a.1 && b.0 < a.0);
+                Ok(if b_wins { b } else { a })
+            });
+        
+        optimum.unwrap_or(Err(OptimizerError::EmptyRange))
+    }
+    
+    // Find optimal batch size with a ternary search, using O(log n) efficiency
+    // evaluations instead of a full scan. Assumes efficiency is unimodal in batch
+    // size (rises to a single peak, then falls), as it is under this model; on a
+    // curve with several peaks the result may be a local maximum.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn optimize_batch_size_fast(&self, precision: Precision, min_batch: u32, max_batch: u32) -> Result<(u32, f64), OptimizerError> {
+        check_nonempty_range(min_batch, max_batch)?;
+        let (mut low, mut high) = (min_batch, max_batch);
+        
+        while high.saturating_sub(low) > 2 {
+            let third = (high - low) / 3;
+            let (left, right) = (low + third, high - third);
+            let (left_efficiency, right_efficiency) = (self.efficiency(left, precision)?, self.efficiency(right, precision)?);
+            #[cfg(feature = "tracing")]
+            tracing::debug!(low, high, left, left_efficiency, right, right_efficiency, "narrowing search");
+            if left_efficiency < right_efficiency {
+                low = left + 1;
+            } else {
+                high = right;
+            }
+        }
+        
+        // Scan the few remaining candidates directly
+        let mut optimal_batch = low;
+        let mut max_efficiency = self.efficiency(low, precision)?;
+        for batch_size in (low..=high).skip(1) {
+            let efficiency = self.efficiency(batch_size, precision)?;
+            if efficiency > max_efficiency {
+                max_efficiency = efficiency;
+                optimal_batch = batch_size;
+            }
+            #[cfg(feature = "tracing")]
+            tracing::debug!(batch_size, efficiency, best_batch = optimal_batch, best_efficiency = max_efficiency, "evaluated batch size");
+        }
+        
+        Ok((optimal_batch, max_efficiency))
+    }
+    
+    // Find the cheapest (num_replicas, batch_per_replica, total_power) serving a target
+    // throughput in samples per second. Each replica pays its own base power, so for
+    // every batch size only the smallest replica count that meets the target is kept.
+    // Replicas are assumed to run back to back at full power, which fits a fleet kept
+    // busy by a request backlog; recommend_fleet is the steady-state variant
+    // that charges idle power between batches and requires batches to fill from arrivals.
+    pub fn optimal_replicas(&self, target_sps: f64, precision: Precision, min_batch: u32, max_batch: u32) -> Result<(u32, u32, f64), OptimizerError> {
+        if !(target_sps > 0.0 && target_sps.is_finite()) {
+            return Err(OptimizerError::InvalidServingLoad);
+        }
+        check_scan_range(min_batch, max_batch)?;
+        let mut best: Option<(u32, u32, f64)> = None;
+        
+        for batch_size in min_batch..=max_batch {
+            let breakdown = self.energy_breakdown(batch_size, precision)?;
+            let replica_power = breakdown.total_power();
+            let replica_throughput = batch_size as f64 / breakdown.inference_time;
+            let num_replicas = (target_sps / replica_throughput).ceil();
+            if num_replicas > u32::MAX as f64 {
+                continue;
+            }
+            let total_power = num_replicas * replica_power;
+            
+            if best.is_none_or(|(_, _, best_power)| total_power < best_power) {
+                best = Some((num_replicas as u32, batch_size, total_power));
+            }
+        }
+        
+        best.ok_or(OptimizerError::Overloaded)
+    }
+    
+    // Energy for an LLM request split into prefill and decode phases.
+    // Prefill runs all prompt tokens of the batch in one parallel, compute-bound pass,
+    // so it is modeled as a single inference over batch_size * prompt_len samples.
+    // Decode is memory-bound: every generated token is one step at batch_size that
+    // streams the weights plus the growing KV cache, so each step lasts
+    // (memory_usage + kv_cache) / memory_bandwidth at the batch's total power.
+    pub fn llm_request_energy(&self, prompt_len: u32, gen_len: u32, batch_size: u32, precision: Precision) -> Result<LlmEnergyBreakdown, OptimizerError> {
+        let prefill_energy = self.prefill_energy(prompt_len, batch_size, precision)?;
+        let decode_energy = self.decode_energy_after(prompt_len, gen_len, batch_size, precision)?;
+        
+        Ok(LlmEnergyBreakdown {
+            prefill_energy,
+            decode_energy,
+            total_energy: prefill_energy + decode_energy,
+        })
+    }
+    
+    // Joules of the compute-bound prefill phase: all prompt tokens of the batch in one
+    // parallel pass (the prefill half of llm_request_energy)
+    pub fn prefill_energy(&self, prompt_tokens: u32, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        let batch_size = BatchSize::try_from(batch_size)?.get();
+        if prompt_tokens == 0 {
+            return Ok(0.0);
+        }
+        self.energy_consumption(batch_size.saturating_mul(prompt_tokens), precision)
+    }
+    
+    // Joules of the memory-bound decode phase starting from an empty KV cache: one
+    // sequential step per token, each lasting (memory_usage + kv_cache) / memory_bandwidth,
+    // so lower bandwidth stretches every step
+    pub fn decode_energy(&self, generated_tokens: u32, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        self.decode_energy_after(0, generated_tokens, batch_size, precision)
+    }
+    
+    // Prefill plus decode joules of one request; unlike prefill_energy + decode_energy,
+    // decoding starts with the prompt's tokens already in the KV cache
+    pub fn total_inference_energy(&self, prompt_tokens: u32, generated_tokens: u32, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        Ok(self.llm_request_energy(prompt_tokens, generated_tokens, batch_size, precision)?.total_energy)
+    }
+    
+    // Joules to generate tokens at batch 1 with speculative decoding: each round the draft
+    // model proposes SPECULATION_LENGTH tokens and this model verifies them in one step,
+    // which costs about one decode step since decode is bound by streaming the weights.
+    // With each draft token accepted independently at acceptance_rate a, a round yields
+    // (1 - a^(L+1)) / (1 - a) tokens on average, the accepted prefix plus one token
+    // from the verifier. Step energies are decode_energy averages over the generation.
+    pub fn speculative_energy(&self, draft: &Optimizer, tokens: u32, acceptance_rate: f64, precision: Precision) -> Result<f64, OptimizerError> {
+        if !(0.0..=1.0).contains(&acceptance_rate) {
+            return Err(OptimizerError::InvalidAcceptanceRate);
+        }
+        if tokens == 0 {
+            return Ok(0.0);
+        }
+        let (draft_step, target_step) = self.speculative_step_energies(draft, tokens, precision)?;
+        let rounds = tokens as f64 / expected_tokens_per_round(acceptance_rate);
+        Ok(rounds * (SPECULATION_LENGTH as f64 * draft_step + target_step))
+    }
+    
+    // Lowest acceptance rate at which speculative_energy beats plain decode_energy at
+    // batch 1, or None when the draft model's steps are too costly to ever pay off
+    pub fn speculative_break_even(&self, draft: &Optimizer, tokens: u32, precision: Precision) -> Result<Option<f64>, OptimizerError> {
+        if tokens == 0 {
+            return Ok(None);
+        }
+        let (draft_step, target_step) = self.speculative_step_energies(draft, tokens, precision)?;
+        // Speculation wins once a round yields more tokens than its cost in target steps
+        let required = 1.0 + SPECULATION_LENGTH as f64 * draft_step / target_step;
+        if expected_tokens_per_round(1.0) <= required {
+            return Ok(None);
+        }
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..64 {
+            let mid = 0.5 * (low + high);
+            if expected_tokens_per_round(mid) > required {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        Ok(Some(high))
+    }
+    
+    // Mean per-token decode joules of the draft model and of this (target) model
+    fn speculative_step_energies(&self, draft: &Optimizer, tokens: u32, precision: Precision) -> Result<(f64, f64), OptimizerError> {
+        let draft_step = draft.decode_energy(tokens, 1, precision)? / tokens as f64;
+        let target_step = self.decode_energy(tokens, 1, precision)? / tokens as f64;
+        Ok((draft_step, target_step))
+    }
+    
+    // Decode joules for gen_len tokens after a prompt_len-token context
+    fn decode_energy_after(&self, prompt_len: u32, gen_len: u32, batch_size: u32, precision: Precision) -> Result<f64, OptimizerError> {
+        let (_, memory_bandwidth_factor) = self.precision_factors(precision);
+        let step_power = self.energy_breakdown(batch_size, precision)?.total_power();
+        
+        let mut decode_energy = 0.0;
+        for step in 0..gen_len {
+            let context_len = prompt_len as f64 + step as f64;
+            let kv_cache = batch_size as f64 * context_len * KV_CACHE_GB_PER_TOKEN;
+            let step_time = (self.model_params.memory_usage + kv_cache) * memory_bandwidth_factor
+                / self.model_params.memory_bandwidth;
+            decode_energy += step_power * step_time;
+        }
+        
+        Ok(decode_energy)
+    }
+    
+    // Energy (joules) to generate num_tokens tokens autoregressively, one forward pass
+    // of batch_size per token. Step t runs with memory_usage grown by the KV cache of
+    // t tokens per sequence, so memory power rises with sequence position and comes to
+    // dominate long generations. Unlike llm_request_energy's decode phase, each step
+    // keeps the full energy_consumption timing rather than a bandwidth-bound one.
+    pub fn generation_energy(&self, batch_size: u32, precision: Precision, num_tokens: u32) -> Result<f64, OptimizerError> {
+        let batch_size = BatchSize::try_from(batch_size)?.get();
+        
+        let mut step_optimizer = self.clone();
+        let mut total_energy = 0.0;
+        for step in 0..num_tokens {
+            let kv_cache = batch_size as f64 * step as f64 * KV_CACHE_GB_PER_TOKEN;
+            step_optimizer.model_params.memory_usage = self.model_params.memory_usage + kv_cache;
+            total_energy += step_optimizer.energy_consumption(batch_size, precision)?;
+        }
+        
+        Ok(total_energy)
+    }
+    
+    // Energy (joules) of one batch with the model sharded across num_devices. Each device
+    // holds 1/n of the weights and finishes its share of compute in 1/n of the time, then
+    // all devices join a ring all-reduce moving 2(n-1)/n of the model (memory_usage GB)
+    // per device over interconnect_gbps (gigabits/s). Devices idle at idle power while
+    // they synchronize, and the link spends INTERCONNECT_JOULES_PER_GB on the transfer.
+    // Sharding saves memory power but communication grows with n, so past a few devices
+    // the total energy rises.
+    pub fn energy_consumption_distributed(&self, batch_size: u32, precision: Precision, num_devices: u32, interconnect_gbps: f64) -> Result<f64, OptimizerError> {
+        if num_devices == 0 {
+            return Err(OptimizerError::ZeroDeviceCount);
+        }
+        if interconnect_gbps.is_nan() || interconnect_gbps <= 0.0 {
+            return Err(OptimizerError::NonPositiveBandwidth);
+        }
+        let devices = num_devices as f64;
+        
+        let mut shard = self.clone();
+        shard.model_params.memory_usage /= devices;
+        shard.model_params.inference_time /= devices;
+        let compute_energy = devices * shard.energy_consumption(batch_size, precision)?;
+        
+        let gb_per_device = 2.0 * (devices - 1.0) / devices * self.model_params.memory_usage;
+        let sync_time = gb_per_device * 8.0 / interconnect_gbps;
+        let sync_energy = devices * (self.idle_power() * sync_time + gb_per_device * INTERCONNECT_JOULES_PER_GB);
+        
+        Ok(compute_energy + sync_energy)
+    }
+    
+    // Grams of CO2 emitted per sample, given the grid's carbon intensity in g CO2 per kWh
+    pub fn co2_per_inference(&self, batch_size: u32, precision: Precision, grid_intensity_g_per_kwh: f64) -> Result<f64, OptimizerError> {
+        if grid_intensity_g_per_kwh.is_nan() || grid_intensity_g_per_kwh < 0.0 {
+            return Err(OptimizerError::NegativeGridIntensity);
+        }
+        let energy_kwh = self.energy_consumption(batch_size, precision)? / JOULES_PER_KWH;
+        Ok(energy_kwh * grid_intensity_g_per_kwh / batch_size as f64)
+    }
+    
+    // Grams of CO2 emitted per million samples
+    pub fn co2_per_million_inferences(&self, batch_size: u32, precision: Precision, grid_intensity_g_per_kwh: f64) -> Result<f64, OptimizerError> {
+        Ok(self.co2_per_inference(batch_size, precision, grid_intensity_g_per_kwh)? * 1e6)
+    }
+    
+    // Electricity cost per sample, in the currency of `price_per_kwh` (e.g. dollars per
+    // kWh in, dollars per sample out). Only energy drawn by the accelerator is counted.
+    pub fn cost_per_inference(&self, batch_size: u32, precision: Precision, price_per_kwh: f64) -> Result<f64, OptimizerError> {
+        if price_per_kwh.is_nan() || price_per_kwh < 0.0 {
+            return Err(OptimizerError::NegativePrice);
+        }
+        let energy_kwh = self.energy_consumption(batch_size, precision)? / JOULES_PER_KWH;
+        Ok(energy_kwh * price_per_kwh / batch_size as f64)
+    }
+    
+    // Electricity cost per million samples, in the currency of `price_per_kwh`
+    pub fn cost_per_million_inferences(&self, batch_size: u32, precision: Precision, price_per_kwh: f64) -> Result<f64, OptimizerError> {
+        Ok(self.cost_per_inference(batch_size, precision, price_per_kwh)? * 1e6)
+    }
+    
+    // Render energy, efficiency, power, and latency in Prometheus text exposition format
+    pub fn prometheus_metrics(&self, batch_size: u32, precision: Precision) -> Result<String, OptimizerError> {
+        let breakdown = self.energy_breakdown(batch_size, precision)?;
+        let (total_power, inference_time, energy) =
+            (breakdown.total_power(), breakdown.inference_time, breakdown.total_energy);
+        let labels = format!("batch=\"{}\",precision=\"{}\"",
+            batch_size, escape_label_value(precision.as_str()));
+        
+        let metrics = [
+            ("ai_energy_joules", "Modeled energy per batch inference in joules.", energy),
+            ("ai_energy_efficiency_samples_per_joule", "Modeled samples processed per joule.", batch_size as f64 / energy),
+            ("ai_energy_power_watts", "Modeled total power draw in watts.", total_power),
+            ("ai_energy_latency_seconds", "Modeled inference time per batch in seconds.", inference_time),
+        ];
+        
+        let mut output = String::new();
+        for (name, help, value) in metrics {
+            output.push_str(&format!("# HELP {} {}\n", name, help));
+            output.push_str(&format!("# TYPE {} gauge\n", name));
+            output.push_str(&format!("{}{{{}}} {}\n", name, labels, value));
+        }
+        Ok(output)
+    }
+    
+    // Export data for batch sizes and precisions to CSV. The file exporters are compiled
+    // out on wasm32-unknown-unknown, which has no filesystem; write_data still works.
+    #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    pub fn export_data(&self, min_batch: u32, max_batch: u32, filename: &str) -> std::io::Result<()> {
+        self.export_data_with(min_batch, max_batch, 1, &DEFAULT_EXPORT_PRECISIONS, filename)
+    }
+    
+    // Export the same data as export_data as a JSON array of
+    // {batch_size, energy: {precision: joules}, efficiency: {precision: samples/J}}
+    #[cfg(all(feature = "serde", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    pub fn export_json(&self, min_batch: u32, max_batch: u32, filename: &str) -> std::io::Result<()> {
+        check_nonempty_range(min_batch, max_batch)?;
+        let mut records = Vec::new();
+        for batch_size in min_batch..=max_batch {
+            let metrics = self.batch_metrics(batch_size, &DEFAULT_EXPORT_PRECISIONS)?;
+            let mut record = JsonRecord { batch_size, energy: BTreeMap::new(), efficiency: BTreeMap::new() };
+            for (precision, (energy, efficiency)) in DEFAULT_EXPORT_PRECISIONS.iter().zip(metrics) {
+                record.energy.insert(precision.as_str(), energy);
+                record.efficiency.insert(precision.as_str(), efficiency);
+            }
+            records.push(record);
+        }
+        
+        let mut file = File::create(filename)?;
+        serde_json::to_writer_pretty(&mut file, &records)?;
+        writeln!(file)?;
+        Ok(())
+    }
+    
+    // (energy, efficiency) at one batch size for each precision, shared by the exporters
+    #[cfg(feature = "std")]
+    fn batch_metrics(&self, batch_size: u32, precisions: &[Precision]) -> Result<Vec<(f64, f64)>, OptimizerError> {
+        precisions
+            .iter()
+            .map(|&precision| {
+                let energy = self.energy_consumption(batch_size, precision)?;
+                Ok((energy, batch_size as f64 / energy))
+            })
+            .collect()
+    }
+    
+    // Export data to CSV with additional BF16, FP8, and FP64 columns
+    #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    pub fn export_data_extended(&self, min_batch: u32, max_batch: u32, filename: &str) -> std::io::Result<()> {
+        self.export_data_with(min_batch, max_batch, 1,
+            &[Precision::Fp32, Precision::Fp16, Precision::Int8, Precision::Int4, Precision::Bf16, Precision::Fp8, Precision::Fp64], filename)
+    }
+    
+    // Export every `step`-th batch size to CSV, with one energy column and then one
+    // efficiency column per requested precision, in the order given
+    #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    pub fn export_data_with(&self, min_batch: u32, max_batch: u32, step: u32, precisions: &[Precision], filename: &str) -> std::io::Result<()> {
+        if step == 0 {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "batch step must be at least 1"));
+        }
+        check_nonempty_range(min_batch, max_batch)?;
+        let mut file = File::create(filename)?;
+        self.write_csv(min_batch, max_batch, step, precisions, &mut file)
+    }
+    
+    // Write the export_data CSV for the given precisions to any writer (stdout, a buffer, ...)
+    #[cfg(feature = "std")]
+    pub fn write_data<W: Write>(&self, min_batch: u32, max_batch: u32, precisions: &[Precision], writer: &mut W) -> std::io::Result<()> {
+        check_nonempty_range(min_batch, max_batch)?;
+        self.write_csv(min_batch, max_batch, 1, precisions, writer)
+    }
+    
+    // Energy columns followed by efficiency columns for each precision
+    #[cfg(feature = "std")]
+    fn write_csv<W: Write>(&self, min_batch: u32, max_batch: u32, step: u32, precisions: &[Precision], writer: &mut W) -> std::io::Result<()> {
+        let mut header = String::from("batch_size");
+        for precision in precisions {
+            header.push_str(&format!(",{}_energy", precision.as_str()));
+        }
+        for precision in precisions {
+            header.push_str(&format!(",{}_efficiency", precision.as_str()));
+        }
+        writeln!(writer, "{}", header)?;
+        
+        for batch_size in (min_batch..=max_batch).step_by(step as usize) {
+            let metrics = self.batch_metrics(batch_size, precisions)?;
+            let mut row = batch_size.to_string();
+            for (energy, _) in &metrics {
+                row.push_str(&format!(",{}", energy));
+            }
+            for (_, efficiency) in &metrics {
+                row.push_str(&format!(",{}", efficiency));
+            }
+            writeln!(writer, "{}", row)?;
+        }
+        
+        Ok(())
+    }
+}
+
+// Requests per second above which replacing `old` with `new` lowers lifetime carbon.
+// The new chip's embodied carbon (kg CO2) must be repaid by operational savings at
+// `grid_intensity` (g CO2 per kWh) over HARDWARE_LIFETIME_SECONDS. Returns infinity
+// when the new chip uses at least as much energy per request and never pays back.
+pub fn upgrade_breakeven_utilization(old: &Optimizer, new: &Optimizer, batch_size: u32, precision: Precision,
+                                     embodied_carbon_kg: f64, grid_intensity: f64) -> Result<f64, OptimizerError> {
+    if grid_intensity.is_nan() || grid_intensity < 0.0 {
+        return Err(OptimizerError::NegativeGridIntensity);
+    }
+    if !embodied_carbon_kg.is_finite() || embodied_carbon_kg < 0.0 {
+        return Err(OptimizerError::InvalidEmbodiedCarbon);
+    }
+    let per_request = |optimizer: &Optimizer| -> Result<f64, OptimizerError> {
+        Ok(optimizer.energy_consumption(batch_size, precision)? / batch_size as f64)
+    };
+    let saved_joules = per_request(old)? - per_request(new)?;
+    let saved_kg_per_request = saved_joules / JOULES_PER_KWH * grid_intensity / 1000.0;
+    
+    if saved_kg_per_request <= 0.0 {
+        return Ok(f64::INFINITY);
+    }
+    
+    Ok(embodied_carbon_kg / (saved_kg_per_request * HARDWARE_LIFETIME_SECONDS))
+}
+
+// Number of inferences at which chips `a` and `b` reach the same total cost of
+// ownership, capex plus energy at price_per_kwh, both serving at this batch size.
+// None when one chip is at least as cheap at every positive volume.
+pub fn break_even_volume(a: &Optimizer, b: &Optimizer, precision: Precision, batch_size: u32,
+                         price_per_kwh: f64, capex_a: f64, capex_b: f64) -> Result<Option<f64>, OptimizerError> {
+    let marginal_a = a.cost_per_inference(batch_size, precision, price_per_kwh)?;
+    let marginal_b = b.cost_per_inference(batch_size, precision, price_per_kwh)?;
+    
+    // capex_a + n * marginal_a = capex_b + n * marginal_b
+    let volume = (capex_b - capex_a) / (marginal_a - marginal_b);
+    if volume.is_finite() && volume > 0.0 {
+        Ok(Some(volume))
+    } else {
+        Ok(None)
+    }
+}
+
+// Reject empty ranges (min > max) and ranges too long for an exhaustive scan
+pub(crate) fn check_scan_range(min_batch: u32, max_batch: u32) -> Result<(), OptimizerError> {
+    check_nonempty_range(min_batch, max_batch)?;
+    if max_batch as u64 + 1 > min_batch as u64 + MAX_SCAN_BATCHES {
+        return Err(OptimizerError::RangeTooLarge);
+    }
+    Ok(())
+}
+
+// Reject min_batch > max_batch, which would otherwise scan or export nothing
+fn check_nonempty_range(min_batch: u32, max_batch: u32) -> Result<(), OptimizerError> {
+    if min_batch > max_batch {
+        return Err(OptimizerError::EmptyRange);
+    }
+    Ok(())
+}
+
+// metric(b + 1) - metric(b), or metric(b) - metric(b - 1) at u32::MAX
+fn forward_difference(batch_size: u32, metric: impl Fn(u32) -> Result<f64, OptimizerError>) -> Result<f64, OptimizerError> {
+    let batch_size = BatchSize::try_from(batch_size)?.get();
+    match batch_size.checked_add(1) {
+        Some(next) => Ok(metric(next)? - metric(batch_size)?),
+        None => Ok(metric(batch_size)? - metric(batch_size - 1)?),
+    }
+}
+
+// Arrhenius leakage relative to REFERENCE_TEMPERATURE_C: exp(Ea/k * (1/T_ref - 1/T)).
+// T must be a positive absolute temperature; at 0 K and below 1/T blows up or flips sign.
+fn leakage_scale(temperature_c: f64) -> Result<f64, OptimizerError> {
+    let temperature_k = temperature_c + CELSIUS_TO_KELVIN;
+    if !(temperature_k > 0.0 && temperature_k.is_finite()) {
+        return Err(OptimizerError::InvalidTemperature);
+    }
+    let reference_k = REFERENCE_TEMPERATURE_C + CELSIUS_TO_KELVIN;
+    Ok((LEAKAGE_ACTIVATION_ENERGY_EV / BOLTZMANN_CONSTANT_EV * (1.0 / reference_k - 1.0 / temperature_k)).exp())
+}
+
+// Mean tokens produced per speculative round, (1 - a^(L+1)) / (1 - a), or L + 1 at a = 1
+fn expected_tokens_per_round(acceptance_rate: f64) -> f64 {
+    let length = SPECULATION_LENGTH as f64;
+    if acceptance_rate >= 1.0 {
+        length + 1.0
+    } else {
+        (1.0 - acceptance_rate.powf(length + 1.0)) / (1.0 - acceptance_rate)
+    }
+}
+
+// Value at quantile q of ascending, non-empty values, interpolating between ranks
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let rank = q * (sorted.len() - 1) as f64;
+    let below = rank.floor() as usize;
+    let above = (below + 1).min(sorted.len() - 1);
+    sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f64)
+}
+
+// Smallest and largest value, for min-max normalization
+fn min_max(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), value| (low.min(value), high.max(value)))
+}
+
+// Map value onto [0, 1] within (low, high); a metric flat over the range maps to 0
+fn normalize(value: f64, (low, high): (f64, f64)) -> f64 {
+    if high > low { (value - low) / (high - low) } else { 0.0 }
+}
+
+// Escape a Prometheus label value (backslash, double quote, and newline)
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{PrecisionFactors, ThermalModel};
+    
+    fn optimizer() -> Optimizer {
+        Optimizer::new(ModelParams::default()).unwrap()
+    }
+    
+    #[test]
+    fn power_headroom_never_grows_with_batch_size() {
+        let sweep = optimizer().power_headroom_sweep(Precision::Fp32, 1, 256).unwrap();
+        assert_eq!(sweep.len(), 256);
+        for pair in sweep.windows(2) {
+            assert!(pair[1].1 <= pair[0].1, "headroom rose from batch {} to {}", pair[0].0, pair[1].0);
+        }
+    }
+    
+    #[test]
+    fn low_throughput_target_needs_a_single_replica() {
+        let optimizer = optimizer();
+        let (replicas, batch_size, total_power) = optimizer.optimal_replicas(1.0, Precision::Fp16, 1, 64).unwrap();
+        assert_eq!(replicas, 1);
+        assert!(optimizer.throughput(batch_size, Precision::Fp16).unwrap() >= 1.0);
+        assert_eq!(total_power, optimizer.energy_breakdown(batch_size, Precision::Fp16).unwrap().total_power());
+    }
+    
+    #[test]
+    fn optimal_replicas_rejects_bad_targets_and_ranges() {
+        let optimizer = optimizer();
+        for target in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(optimizer.optimal_replicas(target, Precision::Fp16, 1, 64), Err(OptimizerError::InvalidServingLoad));
+        }
+        assert_eq!(optimizer.optimal_replicas(10.0, Precision::Fp16, 100, 10), Err(OptimizerError::EmptyRange));
+        assert_eq!(optimizer.optimal_replicas(10.0, Precision::Fp16, 1, u32::MAX), Err(OptimizerError::RangeTooLarge));
+    }
+    
+    #[test]
+    fn thermal_stability_holds_only_well_below_tdp() {
+        let optimizer = optimizer();
+        assert!(optimizer.is_thermally_stable(1, Precision::Fp32).unwrap());
+        assert!(!optimizer.is_thermally_stable(512, Precision::Fp32).unwrap());
+        assert!(optimizer.power_draw(512, Precision::Fp32).unwrap().is_throttled());
+    }
+    
+    #[test]
+    fn prometheus_output_is_well_formed() {
+        let optimizer = optimizer();
+        let output = optimizer.prometheus_metrics(16, Precision::Int8).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 12);
+        for metric in lines.chunks(3) {
+            let name = metric[0].strip_prefix("# HELP ").unwrap().split(' ').next().unwrap();
+            assert_eq!(metric[1], format!("# TYPE {} gauge", name));
+            let (series, value) = metric[2].split_once(' ').unwrap();
+            assert_eq!(series, format!("{}{{batch=\"16\",precision=\"int8\"}}", name));
+            assert!(value.parse::<f64>().unwrap().is_finite());
+        }
+        
+        let joules = lines[2].split_once(' ').unwrap().1.parse::<f64>().unwrap();
+        assert_eq!(joules, optimizer.energy_consumption(16, Precision::Int8).unwrap());
+    }
+    
+    #[test]
+    fn prometheus_label_values_are_escaped() {
+        assert_eq!(escape_label_value("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+    
+    #[test]
+    fn no_generated_tokens_means_no_decode_energy() {
+        let optimizer = optimizer();
+        let request = optimizer.llm_request_energy(128, 0, 4, Precision::Fp16).unwrap();
+        assert_eq!(request.decode_energy, 0.0);
+        assert!(request.prefill_energy > 0.0);
+        assert_eq!(request.total_energy, request.prefill_energy);
+        
+        let longer = optimizer.llm_request_energy(128, 64, 4, Precision::Fp16).unwrap();
+        assert_eq!(longer.prefill_energy, request.prefill_energy);
+        assert!(longer.decode_energy > 0.0);
+    }
+    
+    #[test]
+    fn tiny_tdp_stretches_inference_and_caps_power() {
+        let roomy = optimizer();
+        let tiny = Optimizer::new(ModelParams::builder().thermal_design_power(5.0).build().unwrap()).unwrap();
+        for batch_size in [1, 8, 64, 512] {
+            let capped = tiny.energy_breakdown(batch_size, Precision::Fp32).unwrap();
+            assert!(capped.total_power() <= 5.0 * (1.0 + 1e-12));
+            assert!(capped.inference_time > roomy.latency(batch_size, Precision::Fp32).unwrap());
+            
+            let draw = tiny.power_draw(batch_size, Precision::Fp32).unwrap();
+            assert!(draw.is_throttled());
+            assert!(draw.clamped_power <= 5.0 * (1.0 + 1e-12));
+            assert!(draw.unclamped_power > draw.clamped_power);
+        }
+    }
+    
+    #[test]
+    fn fast_search_agrees_with_the_linear_scan() {
+        let optimizer = optimizer();
+        for precision in Precision::ALL {
+            for (min_batch, max_batch) in [(1, 128), (1, 1024), (7, 300), (128, 4096)] {
+                let (scan_batch, scan_efficiency) = optimizer.optimize_batch_size(precision, min_batch, max_batch).unwrap();
+                let (fast_batch, fast_efficiency) = optimizer.optimize_batch_size_fast(precision, min_batch, max_batch).unwrap();
+                assert!(fast_batch.abs_diff(scan_batch) <= 1, "{:?} {}..={}: {} vs {}", precision, min_batch, max_batch, fast_batch, scan_batch);
+                assert!((fast_efficiency - scan_efficiency).abs() <= 1e-9 * scan_efficiency);
+            }
+        }
+    }
+    
+    #[test]
+    fn breakdown_reconciles_with_energy_consumption() {
+        let optimizer = optimizer();
+        for precision in Precision::ALL {
+            for batch_size in [1, 3, 32, 100, 1024] {
+                let breakdown = optimizer.energy_breakdown(batch_size, precision).unwrap();
+                let parts = (breakdown.base_power + breakdown.computation_power + breakdown.memory_power) * breakdown.inference_time;
+                let energy = optimizer.energy_consumption(batch_size, precision).unwrap();
+                assert_eq!(breakdown.total_energy, energy);
+                assert!((parts - energy).abs() <= 1e-12 * energy);
+            }
+        }
+    }
+    
+    #[test]
+    fn nan_grid_intensity_is_rejected() {
+        let optimizer = optimizer();
+        assert_eq!(optimizer.co2_per_inference(8, Precision::Fp32, 0.0), Ok(0.0));
+        assert_eq!(optimizer.co2_per_inference(8, Precision::Fp32, -1.0), Err(OptimizerError::NegativeGridIntensity));
+        assert_eq!(optimizer.co2_per_inference(8, Precision::Fp32, f64::NAN), Err(OptimizerError::NegativeGridIntensity));
+    }
+    
+    #[test]
+    fn nan_price_is_rejected() {
+        let optimizer = optimizer();
+        assert_eq!(optimizer.cost_per_inference(8, Precision::Fp32, -0.1), Err(OptimizerError::NegativePrice));
+        assert_eq!(optimizer.cost_per_inference(8, Precision::Fp32, f64::NAN), Err(OptimizerError::NegativePrice));
+    }
+    
+    #[test]
+    #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    fn export_header_lists_exactly_the_requested_precisions() {
+        let path = std::env::temp_dir().join(format!("ai_energy_export_{}.csv", std::process::id()));
+        let filename = path.to_str().unwrap();
+        optimizer().export_data_with(1, 10, 4, &[Precision::Int4, Precision::Bf16], filename).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "batch_size,int4_energy,bf16_energy,int4_efficiency,bf16_efficiency");
+        let batches: Vec<&str> = lines[1..].iter().map(|line| line.split(',').next().unwrap()).collect();
+        assert_eq!(batches, ["1", "5", "9"]);
+    }
+    
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_data_streams_csv_into_a_buffer() {
+        let optimizer = optimizer();
+        let mut buffer = Vec::new();
+        optimizer.write_data(2, 4, &[Precision::Fp32], &mut buffer).unwrap();
+        let csv = String::from_utf8(buffer).unwrap();
+        
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "batch_size,fp32_energy,fp32_efficiency");
+        for (line, batch_size) in lines[1..].iter().zip(2..) {
+            let energy = optimizer.energy_consumption(batch_size, Precision::Fp32).unwrap();
+            assert_eq!(*line, format!("{},{},{}", batch_size, energy, batch_size as f64 / energy));
+        }
+    }
+    
+    #[test]
+    fn tight_latency_bound_excludes_large_batches() {
+        let optimizer = optimizer();
+        let (unconstrained, _) = optimizer.optimize_batch_size(Precision::Fp16, 1, 256).unwrap();
+        assert!(unconstrained > 4);
+        
+        let bound = optimizer.latency(4, Precision::Fp16).unwrap();
+        let (batch_size, efficiency) = optimizer.optimize_under_latency(Precision::Fp16, 1, 256, bound).unwrap().unwrap();
+        assert_eq!(batch_size, 4);
+        assert!(optimizer.latency(batch_size, Precision::Fp16).unwrap() <= bound);
+        assert_eq!(efficiency, optimizer.efficiency(batch_size, Precision::Fp16).unwrap());
+        
+        let too_tight = optimizer.latency(1, Precision::Fp16).unwrap() / 2.0;
+        assert_eq!(optimizer.optimize_under_latency(Precision::Fp16, 1, 256, too_tight), Ok(None));
+    }
+    
+    #[test]
+    fn pareto_frontier_drops_dominated_batches() {
+        // At FP16 latency grows with batch size while energy per sample bottoms out at
+        // batch 8, so every larger batch is both slower and costlier than batch 8
+        let optimizer = optimizer();
+        let frontier = optimizer.pareto_frontier(Precision::Fp16, 1, 64).unwrap();
+        let batches: Vec<u32> = frontier.iter().map(|&(batch_size, _, _)| batch_size).collect();
+        assert_eq!(batches, (1..=8).collect::<Vec<u32>>());
+        
+        for batch_size in 9..=64 {
+            let latency = optimizer.latency(batch_size, Precision::Fp16).unwrap();
+            let per_sample = optimizer.energy_consumption(batch_size, Precision::Fp16).unwrap() / batch_size as f64;
+            assert!(frontier.iter().any(|&(_, faster, cheaper)| faster < latency && cheaper < per_sample));
+        }
+    }
+    
+    #[test]
+    fn faster_memory_draws_less_memory_power_at_batch_64() {
+        let with_bandwidth = |gb_per_s| Optimizer::new(ModelParams::builder().memory_bandwidth(gb_per_s).build().unwrap()).unwrap();
+        let batch_size = BatchSize::try_from(64).unwrap();
+        let slow = with_bandwidth(256.0).power_demand(batch_size, Precision::Fp32);
+        let fast = with_bandwidth(2048.0).power_demand(batch_size, Precision::Fp32);
+        assert!(fast.memory_power < slow.memory_power * 0.9, "{} vs {}", fast.memory_power, slow.memory_power);
+        assert_eq!(fast.base_power, slow.base_power);
+    }
+    
+    #[test]
+    fn larger_cache_keeps_large_batches_faster() {
+        let with_cache = |megabytes| Optimizer::new(ModelParams::builder().cache_size(megabytes).build().unwrap()).unwrap();
+        let (small, large) = (with_cache(8.0), with_cache(64.0));
+        for batch_size in [16, 64, 256] {
+            let small_energy = small.energy_consumption(batch_size, Precision::Fp32).unwrap();
+            let large_energy = large.energy_consumption(batch_size, Precision::Fp32).unwrap();
+            assert!(large.latency(batch_size, Precision::Fp32).unwrap() < small.latency(batch_size, Precision::Fp32).unwrap());
+            assert!(large_energy < small_energy, "batch {}: {} vs {}", batch_size, large_energy, small_energy);
+        }
+    }
+    
+    #[test]
+    fn amortized_energy_converges_to_steady_state() {
+        let optimizer = Optimizer::new(ModelParams::builder().startup_energy(500.0).build().unwrap()).unwrap();
+        let steady_state = optimizer.energy_consumption(8, Precision::Fp32).unwrap() / 8.0;
+        
+        let mut previous = f64::INFINITY;
+        for total_samples in [1, 10, 1_000, 100_000, u32::MAX] {
+            let amortized = optimizer.amortized_energy_per_sample(8, Precision::Fp32, total_samples).unwrap();
+            assert!(amortized > steady_state && amortized < previous);
+            previous = amortized;
+        }
+        assert!(previous - steady_state < 1e-6);
+        assert_eq!(optimizer.amortized_energy_per_sample(8, Precision::Fp32, 0), Err(OptimizerError::ZeroSampleCount));
+    }
+    
+    #[test]
+    fn sparse_model_uses_less_energy_than_dense() {
+        let dense = optimizer();
+        let sparse = Optimizer::new(ModelParams::builder().sparsity(0.5).build().unwrap()).unwrap();
+        for precision in [Precision::Fp32, Precision::Int8] {
+            for batch_size in [1, 16, 128] {
+                let dense_energy = dense.energy_consumption(batch_size, precision).unwrap();
+                let sparse_energy = sparse.energy_consumption(batch_size, precision).unwrap();
+                assert!(sparse_energy < dense_energy);
+                // Sublinear: halving the weights saves less than half the energy
+                assert!(sparse_energy > dense_energy / 2.0);
+            }
+        }
+    }
+    
+    #[test]
+    fn throughput_grows_then_levels_off() {
+        let optimizer = optimizer();
+        let (peak, peak_throughput) = optimizer.optimize_throughput(Precision::Fp32, 1, 1024).unwrap();
+        assert!(peak > 1);
+        
+        let curve: Vec<f64> = (1..=1024).map(|batch_size| optimizer.throughput(batch_size, Precision::Fp32).unwrap()).collect();
+        assert!(curve[..peak as usize].windows(2).all(|pair| pair[1] > pair[0]));
+        assert!(curve.iter().all(|&throughput| throughput <= peak_throughput));
+        // One more sample adds about 20% at batch 1 but under 2% next to the peak
+        assert!(curve[1] > 1.1 * curve[0]);
+        for neighbour in [peak - 1, peak + 1] {
+            assert!(curve[neighbour as usize - 1] > 0.98 * peak_throughput);
+        }
+    }
+    
+    #[test]
+    fn weighted_search_matches_the_pure_objectives_at_the_extremes() {
+        let optimizer = optimizer();
+        for precision in Precision::ALL {
+            let (energy_batch, _) = optimizer.optimize_batch_size(precision, 1, 512).unwrap();
+            let (throughput_batch, _) = optimizer.optimize_throughput(precision, 1, 512).unwrap();
+            assert_eq!(optimizer.optimize_weighted(precision, 1, 512, 1.0).unwrap().0, energy_batch);
+            assert_eq!(optimizer.optimize_weighted(precision, 1, 512, 0.0).unwrap().0, throughput_batch);
+        }
+        assert_eq!(optimizer.optimize_weighted(Precision::Fp32, 1, 512, 1.5), Err(OptimizerError::InvalidEnergyWeight));
+    }
+    
+    #[test]
+    fn overriding_int8_leaves_other_precisions_alone() {
+        let stock = optimizer();
+        let efficient_int8 = PrecisionFactors { compute: 0.1, ..PrecisionFactors::default_for(Precision::Int8) };
+        let tuned = optimizer().with_precision_profile(PrecisionProfile::default().with(Precision::Int8, efficient_int8)).unwrap();
+        for batch_size in [1, 32, 256] {
+            assert!(tuned.energy_consumption(batch_size, Precision::Int8).unwrap() < stock.energy_consumption(batch_size, Precision::Int8).unwrap());
+            for precision in [Precision::Fp32, Precision::Fp16, Precision::Int4] {
+                assert_eq!(tuned.energy_consumption(batch_size, precision), stock.energy_consumption(batch_size, precision));
+            }
+        }
+    }
+    
+    #[test]
+    fn u32_max_bounds_finish_without_overflow() {
+        let optimizer = optimizer();
+        assert_eq!(optimizer.optimize_batch_size(Precision::Fp32, 1, u32::MAX), Err(OptimizerError::RangeTooLarge));
+        assert_eq!(optimizer.efficiency_curve(Precision::Fp32, 0, u32::MAX), Err(OptimizerError::RangeTooLarge));
+        assert!(optimizer.energy_iter(Precision::Fp32, 1, u32::MAX).is_err());
+        
+        let (batch_size, _) = optimizer.optimize_batch_size(Precision::Fp32, u32::MAX - 3, u32::MAX).unwrap();
+        assert!(batch_size >= u32::MAX - 3);
+        let curve = optimizer.efficiency_curve(Precision::Fp32, u32::MAX - 3, u32::MAX).unwrap();
+        assert_eq!(curve.last().map(|&(batch_size, _)| batch_size), Some(u32::MAX));
+        assert!(optimizer.optimize_batch_size_fast(Precision::Fp32, 1, u32::MAX).is_ok());
+        assert_eq!(optimizer.optimize_batch_size_fast(Precision::Fp32, u32::MAX, u32::MAX).map(|(batch_size, _)| batch_size), Ok(u32::MAX));
+        assert!(optimizer.optimize_batch_size_fast(Precision::Fp32, u32::MAX - 1, u32::MAX).is_ok());
+    }
+    
+    #[test]
+    fn scan_agrees_with_the_efficiency_curve() {
+        let optimizer = optimizer();
+        let curve = optimizer.efficiency_curve(Precision::Int8, 1, 300).unwrap();
+        let best = curve.iter().copied().reduce(|best, point| if point.1 > best.1 { point } else { best }).unwrap();
+        assert_eq!(optimizer.optimize_batch_size(Precision::Int8, 1, 300), Ok(best));
+    }
+    
+    #[test]
+    fn inverted_ranges_are_empty_for_every_sweep() {
+        let optimizer = optimizer();
+        let (min_batch, max_batch) = (64, 8);
+        assert_eq!(optimizer.optimize_batch_size(Precision::Fp32, min_batch, max_batch), Err(OptimizerError::EmptyRange));
+        assert_eq!(optimizer.power_headroom_sweep(Precision::Fp32, min_batch, max_batch), Err(OptimizerError::EmptyRange));
+        assert_eq!(optimizer.optimize_under_latency(Precision::Fp32, min_batch, max_batch, 1.0), Err(OptimizerError::EmptyRange));
+        assert_eq!(optimizer.pareto_frontier(Precision::Fp32, min_batch, max_batch), Err(OptimizerError::EmptyRange));
+        assert_eq!(optimizer.optimal_replicas(100.0, Precision::Fp32, min_batch, max_batch), Err(OptimizerError::EmptyRange));
+        assert!(matches!(optimizer.energy_iter(Precision::Fp32, min_batch, max_batch), Err(OptimizerError::EmptyRange)));
+        assert!(matches!(optimizer.efficiency_iter(Precision::Fp32, min_batch, max_batch), Err(OptimizerError::EmptyRange)));
+    }
+    
+    #[test]
+    fn more_devices_give_diminishing_returns() {
+        let optimizer = optimizer();
+        let energies: Vec<f64> = (1..=8)
+            .map(|devices| optimizer.energy_consumption_distributed(64, Precision::Fp32, devices, 1000.0).unwrap())
+            .collect();
+        assert_eq!(energies[0], optimizer.energy_consumption(64, Precision::Fp32).unwrap());
+        
+        // Each extra device saves less than the one before, until communication wins
+        let savings: Vec<f64> = energies.windows(2).map(|pair| pair[0] - pair[1]).collect();
+        assert!(savings.windows(2).all(|pair| pair[1] < pair[0]));
+        assert!(savings[0] > 0.0 && savings[6] < 0.0);
+        
+        assert_eq!(optimizer.energy_consumption_distributed(64, Precision::Fp32, 0, 1000.0), Err(OptimizerError::ZeroDeviceCount));
+    }
+    
+    #[test]
+    fn aggressive_cooling_throttles_less_than_passive() {
+        let with_thermal = |onset_batch, max_penalty| {
+            let params = ModelParams::builder().thermal_design_power(1000.0).thermal(ThermalModel { onset_batch, max_penalty });
+            Optimizer::new(params.build().unwrap()).unwrap()
+        };
+        let aggressive = with_thermal(512.0, 0.05);
+        let passive = with_thermal(16.0, 0.6);
+        let batch_size = BatchSize::try_from(128).unwrap();
+        let cool = aggressive.power_demand(batch_size, Precision::Fp32);
+        let hot = passive.power_demand(batch_size, Precision::Fp32);
+        assert!(cool.computation_power < hot.computation_power);
+        assert_eq!(cool.memory_power, hot.memory_power);
+        // Passive cooling is pinned at its ceiling, aggressive cooling still ramping
+        assert!((hot.computation_power / cool.computation_power - 1.6 / (1.0 + 128.0 / 5120.0)).abs() < 1e-9);
+        
+        // A higher TDP delays the onset under the same cooling profile
+        let low_tdp = Optimizer::new(ModelParams::builder().thermal_design_power(100.0).build().unwrap()).unwrap();
+        let high_tdp = Optimizer::new(ModelParams::builder().thermal_design_power(400.0).build().unwrap()).unwrap();
+        let small = BatchSize::try_from(8).unwrap();
+        assert!(high_tdp.power_demand(small, Precision::Fp32).computation_power < low_tdp.power_demand(small, Precision::Fp32).computation_power);
+    }
+    
+    #[test]
+    fn edp_optimum_lies_between_the_energy_and_throughput_optima() {
+        let optimizer = optimizer();
+        let breakdown = optimizer.energy_breakdown(16, Precision::Fp16).unwrap();
+        assert_eq!(optimizer.energy_delay_product(16, Precision::Fp16), Ok(breakdown.total_energy * breakdown.inference_time));
+        
+        for precision in Precision::ALL {
+            let (energy_batch, _) = optimizer.optimize_batch_size(precision, 1, 512).unwrap();
+            let (throughput_batch, _) = optimizer.optimize_throughput(precision, 1, 512).unwrap();
+            let (edp_batch, edp) = optimizer.optimize_edp(precision, 1, 512).unwrap();
+            assert!(energy_batch <= edp_batch && edp_batch <= throughput_batch, "{:?}: {} {} {}", precision, energy_batch, edp_batch, throughput_batch);
+            assert_eq!(optimizer.energy_delay_product_per_sample(edp_batch, precision), Ok(edp));
+        }
+    }
+    
+    #[test]
+    fn cold_cache_costs_at_least_the_warm_energy() {
+        let warm = optimizer();
+        let cold = Optimizer::new(ModelParams::builder().cold_start_penalty(1.5).build().unwrap()).unwrap();
+        for precision in Precision::ALL {
+            for batch_size in [1, 8, 64, 512] {
+                assert_eq!(warm.energy_consumption_cold(batch_size, precision), warm.energy_consumption(batch_size, precision));
+                assert!(cold.energy_consumption_cold(batch_size, precision).unwrap() > cold.energy_consumption(batch_size, precision).unwrap());
+            }
+        }
+    }
+    
+    #[test]
+    #[cfg(feature = "std")]
+    fn int4_is_exported_and_optimized_like_the_other_precisions() {
+        let optimizer = optimizer();
+        let mut buffer = Vec::new();
+        optimizer.write_data(1, 2, &DEFAULT_EXPORT_PRECISIONS, &mut buffer).unwrap();
+        let csv = String::from_utf8(buffer).unwrap();
+        let header = csv.lines().next().unwrap();
+        assert!(header.contains(",int4_energy,") && header.ends_with(",int4_efficiency"));
+        
+        let (int4_batch, int4_efficiency) = optimizer.optimize_batch_size(Precision::Int4, 1, 256).unwrap();
+        let (int8_batch, int8_efficiency) = optimizer.optimize_batch_size(Precision::Int8, 1, 256).unwrap();
+        assert!(int4_efficiency > int8_efficiency);
+        assert!(int4_batch >= int8_batch);
+    }
+    
+    #[test]
+    fn memory_bound_high_base_power_profile_has_its_minimum_past_batch_1() {
+        assert_eq!(optimizer().min_energy_batch(Precision::Fp32, 1, 64).unwrap().0, 1);
+        
+        let params = ModelParams::builder().base_power_consumption(500.0).thermal_design_power(2000.0).memory_bandwidth(1.0);
+        let memory_bound = PrecisionFactors { memory_bandwidth: 1.9, ..PrecisionFactors::default_for(Precision::Fp32) };
+        let optimizer = Optimizer::new(params.build().unwrap())
+            .unwrap()
+            .with_precision_profile(PrecisionProfile::default().with(Precision::Fp32, memory_bound))
+            .unwrap();
+        let (batch_size, energy) = optimizer.min_energy_batch(Precision::Fp32, 1, 64).unwrap();
+        assert!(batch_size > 1);
+        assert!(energy < optimizer.energy_consumption(1, Precision::Fp32).unwrap());
+        assert!((1..=64).all(|other| optimizer.energy_consumption(other, Precision::Fp32).unwrap() >= energy));
+    }
+    
+    #[test]
+    fn leakage_rises_with_temperature_above_absolute_zero() {
+        let optimizer = optimizer();
+        assert_eq!(optimizer.leakage_power(25.0), Ok(50.0));
+        let warm = optimizer.leakage_power(35.0).unwrap();
+        assert!(warm > 1.8 * 50.0, "{}", warm);
+        assert!(optimizer.leakage_power(90.0).unwrap() > optimizer.leakage_power(40.0).unwrap());
+        
+        for temperature in [-273.15, -300.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(optimizer.leakage_power(temperature), Err(OptimizerError::InvalidTemperature));
+            assert_eq!(optimizer.energy_consumption_at_temp(8, Precision::Fp32, temperature), Err(OptimizerError::InvalidTemperature));
+        }
+        assert!(optimizer.leakage_power(-273.0).unwrap().is_finite());
+    }
+    
+    #[test]
+    fn training_step_costs_more_than_inference() {
+        let optimizer = optimizer();
+        for precision in Precision::ALL {
+            for batch_size in [1, 16, 256] {
+                let inference = optimizer.energy_consumption(batch_size, precision).unwrap();
+                let training = optimizer.training_step_energy(batch_size, precision).unwrap();
+                // At least the forward/backward multiplier, plus gradient memory
+                assert!(training > 3.0 * inference, "{:?} batch {}: {} vs {}", precision, batch_size, training, inference);
+            }
+        }
+        let lighter = Optimizer::new(ModelParams::builder().training_compute_multiplier(2.0).build().unwrap()).unwrap();
+        assert!(lighter.training_step_energy(16, Precision::Fp32).unwrap() < optimizer.training_step_energy(16, Precision::Fp32).unwrap());
+    }
+    
+    #[test]
+    fn global_search_picks_int8_when_it_wins_everywhere() {
+        let optimizer = optimizer();
+        for batch_size in 1..=128 {
+            assert!(optimizer.efficiency(batch_size, Precision::Int8).unwrap() > optimizer.efficiency(batch_size, Precision::Fp32).unwrap());
+        }
+        let (batch_size, precision, efficiency) = optimizer.optimize_global(1, 128, &[Precision::Fp32, Precision::Int8]).unwrap().unwrap();
+        assert_eq!(precision, Precision::Int8);
+        assert_eq!((batch_size, efficiency), optimizer.optimize_batch_size(Precision::Int8, 1, 128).unwrap());
+        assert_eq!(optimizer.optimize_global(1, 128, &[]), Ok(None));
+    }
+    
+    #[test]
+    fn high_acceptance_speculation_beats_plain_decoding() {
+        let target = Optimizer::new(ModelParams::builder().memory_usage(16.0).build().unwrap()).unwrap();
+        let draft = Optimizer::new(ModelParams::builder().memory_usage(0.5).base_power_consumption(10.0).build().unwrap()).unwrap();
+        let plain = target.decode_energy(256, 1, Precision::Fp16).unwrap();
+        
+        assert!(target.speculative_energy(&draft, 256, 0.9, Precision::Fp16).unwrap() < plain);
+        assert!(target.speculative_energy(&draft, 256, 0.0, Precision::Fp16).unwrap() > plain);
+        
+        let break_even = target.speculative_break_even(&draft, 256, Precision::Fp16).unwrap().unwrap();
+        assert!(break_even > 0.0 && break_even < 0.9);
+        let at_break_even = target.speculative_energy(&draft, 256, break_even, Precision::Fp16).unwrap();
+        assert!((at_break_even - plain).abs() < 1e-6 * plain);
+        assert_eq!(target.speculative_energy(&draft, 256, 1.5, Precision::Fp16), Err(OptimizerError::InvalidAcceptanceRate));
+    }
+    
+    #[test]
+    fn lower_precision_gives_higher_tops_per_watt() {
+        let optimizer = optimizer();
+        let widest_first = [Precision::Fp64, Precision::Fp32, Precision::Fp16, Precision::Int8, Precision::Int4];
+        for batch_size in [1, 8, 64, 512] {
+            let tops: Vec<f64> = widest_first.iter().map(|&precision| optimizer.tops_per_watt(batch_size, precision).unwrap()).collect();
+            for pair in tops.windows(2) {
+                assert!(pair[1] > pair[0], "batch {}: {:?}", batch_size, tops);
+            }
+        }
+    }
+    
+    #[test]
+    #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    fn inverted_ranges_are_rejected_before_exporting() {
+        let optimizer = optimizer();
+        let mut buffer = Vec::new();
+        let error = optimizer.write_data(64, 8, &DEFAULT_EXPORT_PRECISIONS, &mut buffer).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(error.to_string(), OptimizerError::EmptyRange.to_string());
+        assert!(buffer.is_empty());
+        
+        let path = std::env::temp_dir().join(format!("ai_energy_empty_export_{}.csv", std::process::id()));
+        let error = optimizer.export_data(64, 8, path.to_str().unwrap()).unwrap_err();
+        assert_eq!(error.to_string(), OptimizerError::EmptyRange.to_string());
+        assert!(!path.exists());
+    }
+    
+    #[test]
+    fn fleet_scales_out_past_one_replica_and_stays_at_batch_one_when_idle() {
+        let optimizer = optimizer();
+        let max_batch = 128;
+        let single_replica_peak = (1..=max_batch)
+            .map(|batch_size| {
+                let breakdown = optimizer.energy_breakdown(batch_size, Precision::Fp16).unwrap();
+                batch_size as f64 / breakdown.inference_time
+            })
+            .fold(0.0_f64, f64::max);
+        
+        let busy = optimizer.recommend_fleet(2.0 * single_replica_peak, Precision::Fp16, max_batch).unwrap();
+        assert!(busy.replicas > 1, "{:?}", busy);
+        
+        let idle = optimizer.recommend_fleet(0.01, Precision::Fp16, max_batch).unwrap();
+        assert_eq!((idle.replicas, idle.batch_size), (1, 1));
+    }
+    
+    #[test]
+    fn upgrade_pays_back_only_when_the_new_chip_saves_energy() {
+        let old = optimizer();
+        let new = Optimizer::new(ModelParams::builder().base_power_consumption(20.0).build().unwrap()).unwrap();
+        
+        let breakeven = upgrade_breakeven_utilization(&old, &new, 8, Precision::Fp16, 150.0, 400.0).unwrap();
+        assert!(breakeven.is_finite() && breakeven > 0.0);
+        assert_eq!(upgrade_breakeven_utilization(&old, &old, 8, Precision::Fp16, 150.0, 400.0), Ok(f64::INFINITY));
+        assert_eq!(upgrade_breakeven_utilization(&new, &old, 8, Precision::Fp16, 150.0, 400.0), Ok(f64::INFINITY));
+        
+        for grid_intensity in [-1.0, f64::NAN] {
+            assert_eq!(upgrade_breakeven_utilization(&old, &new, 8, Precision::Fp16, 150.0, grid_intensity), Err(OptimizerError::NegativeGridIntensity));
+        }
+        for embodied_carbon_kg in [-1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(upgrade_breakeven_utilization(&old, &new, 8, Precision::Fp16, embodied_carbon_kg, 400.0), Err(OptimizerError::InvalidEmbodiedCarbon));
+        }
+    }
+    
+    #[test]
+    fn roofline_crossover_tracks_bandwidth_and_compute_load() {
+        let params = ModelParams::builder().memory_usage(64.0).memory_bandwidth(100.0).build().unwrap();
+        let optimizer = Optimizer::new(params.clone()).unwrap();
+        let (bound, crossover) = optimizer.operating_point(1, Precision::Fp32).unwrap();
+        assert_eq!(bound, Bound::Memory);
+        assert!(crossover > 1);
+        assert_eq!(optimizer.operating_point(crossover - 1, Precision::Fp32).unwrap().0, Bound::Memory);
+        for batch_size in [crossover, crossover + 1, 4 * crossover] {
+            assert_eq!(optimizer.operating_point(batch_size, Precision::Fp32).unwrap(), (Bound::Compute, crossover));
+        }
+        
+        let faster_memory = Optimizer::new(ModelParams { memory_bandwidth: 400.0, ..params.clone() }).unwrap();
+        assert!(faster_memory.operating_point(1, Precision::Fp32).unwrap().1 < crossover);
+        let heavier_compute = Optimizer::new(ModelParams { computation_factor: 4.0 * params.computation_factor, ..params }).unwrap();
+        assert!(heavier_compute.operating_point(1, Precision::Fp32).unwrap().1 < crossover);
+    }
+    
+    #[test]
+    fn energy_range_matches_energy_consumption_exactly() {
+        let optimizer = optimizer();
+        let (min_batch, max_batch) = (3, 600);
+        for precision in Precision::ALL {
+            let energies = optimizer.energy_range(precision, min_batch, max_batch).unwrap();
+            assert_eq!(energies.len(), (max_batch - min_batch + 1) as usize);
+            for (batch_size, energy) in (min_batch..=max_batch).zip(energies) {
+                assert_eq!(energy, optimizer.energy_consumption(batch_size, precision).unwrap(), "{} at batch {}", precision, batch_size);
+            }
+        }
+    }
+    
+    #[test]
+    fn tight_accuracy_limits_exclude_the_integer_precisions() {
+        let params = ModelParams::builder()
+            .accuracy_delta(Precision::Int8, 1.0)
+            .accuracy_delta(Precision::Int4, 3.0)
+            .accuracy_delta(Precision::Fp8, 1.5)
+            .build()
+            .unwrap();
+        let optimizer = Optimizer::new(params).unwrap();
+        
+        let (_, loose, _) = optimizer.optimize_accuracy_constrained(1, 128, 5.0).unwrap().unwrap();
+        assert_eq!(loose, Precision::Int4);
+        let (_, tight, _) = optimizer.optimize_accuracy_constrained(1, 128, 0.5).unwrap().unwrap();
+        assert!(![Precision::Int8, Precision::Int4, Precision::Fp8].contains(&tight), "{}", tight);
+        
+        for limit in [-0.1, f64::NAN] {
+            assert_eq!(optimizer.optimize_accuracy_constrained(1, 128, limit), Err(OptimizerError::InvalidAccuracyDrop));
+        }
+    }
+}
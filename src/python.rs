@@ -0,0 +1,93 @@
+// Python bindings (`python` feature): `import ai_energy` exposes ModelParams and
+// Optimizer with energy_consumption, efficiency, and optimize_batch_size
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::model::{ModelParams, Precision};
+use crate::optimizer::Optimizer;
+
+// Map any crate error onto a Python ValueError carrying its Display message
+fn value_error(error: impl std::fmt::Display) -> PyErr {
+    PyValueError::new_err(error.to_string())
+}
+
+fn parse_precision(precision: &str) -> PyResult<Precision> {
+    precision.parse().map_err(value_error)
+}
+
+#[pyclass(name = "ModelParams")]
+#[derive(Clone)]
+struct PyModelParams {
+    inner: ModelParams,
+}
+
+#[pymethods]
+impl PyModelParams {
+    // Keyword arguments default to the ModelParamsBuilder defaults
+    #[new]
+    #[pyo3(signature = (*, base_power_consumption=None, computation_factor=None, memory_usage=None,
+                        memory_power_factor=None, inference_time=None, thermal_design_power=None,
+                        cache_size=None, memory_bandwidth=None, startup_energy=None, sparsity=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(base_power_consumption: Option<f64>, computation_factor: Option<f64>, memory_usage: Option<f64>,
+           memory_power_factor: Option<f64>, inference_time: Option<f64>, thermal_design_power: Option<f64>,
+           cache_size: Option<f64>, memory_bandwidth: Option<f64>, startup_energy: Option<f64>,
+           sparsity: Option<f64>) -> PyResult<Self> {
+        let mut builder = ModelParams::builder();
+        if let Some(value) = base_power_consumption { builder = builder.base_power_consumption(value); }
+        if let Some(value) = computation_factor { builder = builder.computation_factor(value); }
+        if let Some(value) = memory_usage { builder = builder.memory_usage(value); }
+        if let Some(value) = memory_power_factor { builder = builder.memory_power_factor(value); }
+        if let Some(value) = inference_time { builder = builder.inference_time(value); }
+        if let Some(value) = thermal_design_power { builder = builder.thermal_design_power(value); }
+        if let Some(value) = cache_size { builder = builder.cache_size(value); }
+        if let Some(value) = memory_bandwidth { builder = builder.memory_bandwidth(value); }
+        if let Some(value) = startup_energy { builder = builder.startup_energy(value); }
+        if let Some(value) = sparsity { builder = builder.sparsity(value); }
+        Ok(PyModelParams { inner: builder.build().map_err(value_error)? })
+    }
+    
+    // Load parameters from a JSON hardware profile
+    #[staticmethod]
+    fn from_json_file(filename: &str) -> PyResult<Self> {
+        Ok(PyModelParams { inner: ModelParams::from_json_file(filename).map_err(value_error)? })
+    }
+    
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.inner)
+    }
+}
+
+#[pyclass(name = "Optimizer")]
+struct PyOptimizer {
+    inner: Optimizer,
+}
+
+#[pymethods]
+impl PyOptimizer {
+    #[new]
+    fn new(params: &PyModelParams) -> PyResult<Self> {
+        Ok(PyOptimizer { inner: Optimizer::new(params.inner.clone()).map_err(value_error)? })
+    }
+    
+    // Precisions are passed by name, e.g. "fp16"
+    fn energy_consumption(&self, batch_size: u32, precision: &str) -> PyResult<f64> {
+        self.inner.energy_consumption(batch_size, parse_precision(precision)?).map_err(value_error)
+    }
+    
+    fn efficiency(&self, batch_size: u32, precision: &str) -> PyResult<f64> {
+        self.inner.efficiency(batch_size, parse_precision(precision)?).map_err(value_error)
+    }
+    
+    // Returns (batch_size, efficiency)
+    fn optimize_batch_size(&self, precision: &str, min_batch: u32, max_batch: u32) -> PyResult<(u32, f64)> {
+        self.inner.optimize_batch_size(parse_precision(precision)?, min_batch, max_batch).map_err(value_error)
+    }
+}
+
+#[pymodule]
+fn ai_energy(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyModelParams>()?;
+    module.add_class::<PyOptimizer>()?;
+    Ok(())
+}
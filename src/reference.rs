@@ -0,0 +1,335 @@
+// Replay of recorded energy curves: load measured energies in the export_data CSV
+// format and compare the model's predictions against them, or load a whole export
+// back as an EnergyDataset to diff runs
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::error::Error;
+#[cfg(feature = "std")]
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::BufRead;
+
+#[cfg(not(any(feature = "std", test)))]
+use crate::math::FloatMath;
+#[cfg(feature = "std")]
+use crate::model::ParseError;
+use crate::model::Precision;
+use crate::optimizer::{Optimizer, OptimizerError};
+
+// One measured energy: joules for a whole batch at a precision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferencePoint {
+    pub batch_size: u32,
+    pub precision: Precision,
+    pub energy: f64, // measured joules
+}
+
+// Predicted vs measured energy at one reference point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointComparison {
+    pub batch_size: u32,
+    pub precision: Precision,
+    pub measured: f64,       // joules
+    pub predicted: f64,      // joules
+    pub error: f64,          // predicted - measured, joules
+    pub relative_error: f64, // error / measured
+}
+
+// Per-point errors and their aggregate; rmse is 0 for an empty reference
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparisonReport {
+    pub points: Vec<PointComparison>,
+    pub rmse: f64, // root-mean-square of the per-point errors, joules
+}
+
+// One batch size of an EnergyDataset; energy and efficiency follow its precisions
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetRow {
+    pub batch_size: u32,
+    pub energy: Vec<f64>,     // joules per batch
+    pub efficiency: Vec<f64>, // samples per joule
+}
+
+// A previously exported energy curve held in memory, independent of any Optimizer
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnergyDataset {
+    pub precisions: Vec<Precision>, // column order of the source file
+    pub rows: Vec<DatasetRow>,      // file order
+}
+
+impl EnergyDataset {
+    // Load a CSV written by export_data (or export_data_with / export_data_extended)
+    #[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+    pub fn from_csv(path: &str) -> Result<EnergyDataset, ReferenceError> {
+        let file = std::fs::File::open(path)?;
+        EnergyDataset::from_reader(std::io::BufReader::new(file))
+    }
+    
+    // from_csv for any buffered reader. Every <precision>_energy column needs a
+    // matching <precision>_efficiency column; an empty input is an empty dataset.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<EnergyDataset, ReferenceError> {
+        let mut lines = reader.lines();
+        let header = match lines.next() {
+            Some(header) => header?,
+            None => return Ok(EnergyDataset { precisions: Vec::new(), rows: Vec::new() }),
+        };
+        let header = ExportHeader::parse(&header)?;
+        
+        let mut efficiency_columns = Vec::with_capacity(header.efficiency_columns.len());
+        for &(column, name) in &header.efficiency_columns {
+            efficiency_columns.push((column, name.parse::<Precision>().map_err(ReferenceError::Precision)?));
+        }
+        let mut columns = Vec::with_capacity(header.energy_columns.len());
+        for &(energy_column, precision) in &header.energy_columns {
+            let efficiency_column = efficiency_columns
+                .iter()
+                .find(|&&(_, other)| other == precision)
+                .map(|&(column, _)| column)
+                .ok_or(ReferenceError::MissingEfficiencyColumn(precision))?;
+            columns.push((energy_column, efficiency_column));
+        }
+        
+        let mut rows = Vec::new();
+        for_each_row(lines, header.batch_column, |batch_size, cells| {
+            let mut row = DatasetRow { batch_size, energy: Vec::with_capacity(columns.len()), efficiency: Vec::with_capacity(columns.len()) };
+            for &(energy_column, efficiency_column) in &columns {
+                row.energy.push(cells.value(energy_column)?);
+                row.efficiency.push(cells.value(efficiency_column)?);
+            }
+            rows.push(row);
+            Ok(())
+        })?;
+        
+        let precisions = header.energy_columns.into_iter().map(|(_, precision)| precision).collect();
+        Ok(EnergyDataset { precisions, rows })
+    }
+    
+    // Batch sizes present, in file order
+    pub fn batch_sizes(&self) -> impl Iterator<Item = u32> + '_ {
+        self.rows.iter().map(|row| row.batch_size)
+    }
+    
+    // Recorded joules per batch, or None if the batch size or precision wasn't exported
+    pub fn energy_at(&self, batch_size: u32, precision: Precision) -> Option<f64> {
+        let (row, column) = self.locate(batch_size, precision)?;
+        Some(row.energy[column])
+    }
+    
+    // Recorded samples per joule, or None if the batch size or precision wasn't exported
+    pub fn efficiency_at(&self, batch_size: u32, precision: Precision) -> Option<f64> {
+        let (row, column) = self.locate(batch_size, precision)?;
+        Some(row.efficiency[column])
+    }
+    
+    // Every recorded energy as a reference point, for compare_to_reference
+    pub fn reference_points(&self) -> Vec<ReferencePoint> {
+        self.rows
+            .iter()
+            .flat_map(|row| {
+                self.precisions
+                    .iter()
+                    .zip(&row.energy)
+                    .map(|(&precision, &energy)| ReferencePoint { batch_size: row.batch_size, precision, energy })
+            })
+            .collect()
+    }
+    
+    // First row with this batch size and the column index of the precision
+    fn locate(&self, batch_size: u32, precision: Precision) -> Option<(&DatasetRow, usize)> {
+        let column = self.precisions.iter().position(|&other| other == precision)?;
+        let row = self.rows.iter().find(|row| row.batch_size == batch_size)?;
+        Some((row, column))
+    }
+}
+
+// Failure reading a reference CSV or an EnergyDataset
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ReferenceError {
+    Io(std::io::Error),
+    MissingBatchColumn,                          // header has no batch_size column
+    MissingEfficiencyColumn(Precision),          // dataset header has <precision>_energy but no <precision>_efficiency
+    Precision(ParseError),                       // an <precision>_energy column names no known precision
+    InvalidValue { line: usize, column: usize }, // missing or unparsable cell (1-based)
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ReferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReferenceError::Io(error) => write!(f, "reference I/O error: {}", error),
+            ReferenceError::MissingBatchColumn => write!(f, "reference CSV has no batch_size column"),
+            ReferenceError::MissingEfficiencyColumn(precision) => {
+                write!(f, "reference CSV has no {}_efficiency column", precision.as_str())
+            }
+            ReferenceError::Precision(error) => write!(f, "reference CSV column: {}", error),
+            ReferenceError::InvalidValue { line, column } => {
+                write!(f, "invalid value at line {}, column {}", line, column)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ReferenceError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReferenceError::Io(error) => Some(error),
+            ReferenceError::Precision(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ReferenceError {
+    fn from(error: std::io::Error) -> Self {
+        ReferenceError::Io(error)
+    }
+}
+
+// Load measured energies from a CSV in the export_data layout: a batch_size column
+// plus one <precision>_energy column per precision (efficiency columns are ignored)
+#[cfg(all(feature = "std", not(all(target_arch = "wasm32", target_os = "unknown"))))]
+pub fn load_reference_csv(path: &str) -> Result<Vec<ReferencePoint>, ReferenceError> {
+    let file = std::fs::File::open(path)?;
+    read_reference_csv(std::io::BufReader::new(file))
+}
+
+// load_reference_csv for any buffered reader
+#[cfg(feature = "std")]
+pub fn read_reference_csv<R: BufRead>(reader: R) -> Result<Vec<ReferencePoint>, ReferenceError> {
+    let mut lines = reader.lines();
+    let header = match lines.next() {
+        Some(header) => header?,
+        None => return Ok(Vec::new()),
+    };
+    let header = ExportHeader::parse(&header)?;
+    
+    let mut points = Vec::new();
+    for_each_row(lines, header.batch_column, |batch_size, cells| {
+        for &(column, precision) in &header.energy_columns {
+            points.push(ReferencePoint { batch_size, precision, energy: cells.value(column)? });
+        }
+        Ok(())
+    })?;
+    
+    Ok(points)
+}
+
+// Column layout of an export_data header shared by read_reference_csv and
+// EnergyDataset::from_reader. Efficiency columns keep their raw precision name, since
+// only the dataset loader uses (and so validates) them.
+#[cfg(feature = "std")]
+struct ExportHeader<'a> {
+    batch_column: usize,
+    energy_columns: Vec<(usize, Precision)>,
+    efficiency_columns: Vec<(usize, &'a str)>,
+}
+
+#[cfg(feature = "std")]
+impl<'a> ExportHeader<'a> {
+    fn parse(header: &'a str) -> Result<Self, ReferenceError> {
+        let mut batch_column = None;
+        let mut energy_columns = Vec::new();
+        let mut efficiency_columns = Vec::new();
+        for (column, name) in header.trim().split(',').enumerate() {
+            if name == "batch_size" {
+                batch_column = Some(column);
+            } else if let Some(precision) = name.strip_suffix("_energy") {
+                energy_columns.push((column, precision.parse().map_err(ReferenceError::Precision)?));
+            } else if let Some(precision) = name.strip_suffix("_efficiency") {
+                efficiency_columns.push((column, precision));
+            }
+        }
+        let batch_column = batch_column.ok_or(ReferenceError::MissingBatchColumn)?;
+        Ok(ExportHeader { batch_column, energy_columns, efficiency_columns })
+    }
+}
+
+// The cells of one data line, with its 1-based line number for errors
+#[cfg(feature = "std")]
+struct RowCells<'a> {
+    cells: Vec<&'a str>,
+    line: usize,
+}
+
+#[cfg(feature = "std")]
+impl RowCells<'_> {
+    fn invalid(&self, column: usize) -> ReferenceError {
+        ReferenceError::InvalidValue { line: self.line, column: column + 1 }
+    }
+    
+    fn value(&self, column: usize) -> Result<f64, ReferenceError> {
+        self.cells.get(column).and_then(|cell| cell.trim().parse().ok()).ok_or_else(|| self.invalid(column))
+    }
+}
+
+// Call `row` with the batch size and cells of every non-empty line after the header
+#[cfg(feature = "std")]
+fn for_each_row<R: BufRead>(lines: std::io::Lines<R>, batch_column: usize,
+                            mut row: impl FnMut(u32, &RowCells) -> Result<(), ReferenceError>) -> Result<(), ReferenceError> {
+    for (index, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells = RowCells { cells: line.trim().split(',').collect(), line: index + 2 };
+        let batch_size = cells
+            .cells
+            .get(batch_column)
+            .and_then(|cell| cell.trim().parse().ok())
+            .ok_or_else(|| cells.invalid(batch_column))?;
+        row(batch_size, &cells)?;
+    }
+    Ok(())
+}
+
+impl Optimizer {
+    // Compare predicted batch energy against each measured reference point
+    pub fn compare_to_reference(&self, reference: &[ReferencePoint]) -> Result<ComparisonReport, OptimizerError> {
+        let mut points = Vec::with_capacity(reference.len());
+        for point in reference {
+            let predicted = self.energy_consumption(point.batch_size, point.precision)?;
+            let error = predicted - point.energy;
+            points.push(PointComparison {
+                batch_size: point.batch_size,
+                precision: point.precision,
+                measured: point.energy,
+                predicted,
+                error,
+                relative_error: error / point.energy,
+            });
+        }
+        
+        let rmse = if points.is_empty() {
+            0.0
+        } else {
+            (points.iter().map(|point| point.error * point.error).sum::<f64>() / points.len() as f64).sqrt()
+        };
+        Ok(ComparisonReport { points, rmse })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::model::ModelParams;
+    
+    #[test]
+    fn both_loaders_read_the_same_export() {
+        let optimizer = Optimizer::new(ModelParams::default()).unwrap();
+        let mut csv = Vec::new();
+        optimizer.write_data(1, 16, &[Precision::Fp32, Precision::Int4], &mut csv).unwrap();
+        
+        let dataset = EnergyDataset::from_reader(csv.as_slice()).unwrap();
+        assert_eq!(read_reference_csv(csv.as_slice()).unwrap(), dataset.reference_points());
+        assert_eq!(dataset.energy_at(16, Precision::Int4), Some(optimizer.energy_consumption(16, Precision::Int4).unwrap()));
+        
+        // Reference files may omit the efficiency columns a dataset needs
+        let energy_only = "batch_size,fp16_energy\n4,1.5\n\n8,x\n";
+        assert!(matches!(read_reference_csv(energy_only.as_bytes()), Err(ReferenceError::InvalidValue { line: 4, column: 2 })));
+        assert!(matches!(EnergyDataset::from_reader(energy_only.as_bytes()), Err(ReferenceError::MissingEfficiencyColumn(Precision::Fp16))));
+    }
+}
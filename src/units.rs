@@ -0,0 +1,120 @@
+// Typed quantities: energy, so callers don't have to remember which unit a bare f64
+// is in, and batch sizes that are non-zero by construction
+use core::fmt;
+use core::num::NonZeroU32;
+use core::ops::Add;
+
+use crate::optimizer::OptimizerError;
+
+const JOULES_PER_WATT_HOUR: f64 = 3600.0;
+const JOULES_PER_KILOWATT_HOUR: f64 = 3.6e6;
+
+// An amount of energy, stored in joules
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Energy(f64);
+
+impl Energy {
+    pub fn joules(joules: f64) -> Self {
+        Energy(joules)
+    }
+    
+    pub fn watt_hours(watt_hours: f64) -> Self {
+        Energy(watt_hours * JOULES_PER_WATT_HOUR)
+    }
+    
+    pub fn kilowatt_hours(kilowatt_hours: f64) -> Self {
+        Energy(kilowatt_hours * JOULES_PER_KILOWATT_HOUR)
+    }
+    
+    pub fn as_joules(self) -> f64 {
+        self.0
+    }
+    
+    pub fn as_watt_hours(self) -> f64 {
+        self.0 / JOULES_PER_WATT_HOUR
+    }
+    
+    pub fn as_kilowatt_hours(self) -> f64 {
+        self.0 / JOULES_PER_KILOWATT_HOUR
+    }
+}
+
+impl Add for Energy {
+    type Output = Energy;
+    
+    fn add(self, other: Energy) -> Energy {
+        Energy(self.0 + other.0)
+    }
+}
+
+impl fmt::Display for Energy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} J", self.0)
+    }
+}
+
+// A batch size of at least one sample. Methods taking BatchSize can't be handed a zero
+// batch, so only the u32 shims check for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BatchSize(NonZeroU32);
+
+impl BatchSize {
+    // None for a batch size of zero
+    pub fn new(batch_size: u32) -> Option<Self> {
+        NonZeroU32::new(batch_size).map(BatchSize)
+    }
+    
+    pub fn get(self) -> u32 {
+        self.0.get()
+    }
+}
+
+impl TryFrom<u32> for BatchSize {
+    type Error = OptimizerError;
+    
+    fn try_from(batch_size: u32) -> Result<Self, OptimizerError> {
+        BatchSize::new(batch_size).ok_or(OptimizerError::ZeroBatchSize)
+    }
+}
+
+impl From<BatchSize> for u32 {
+    fn from(batch_size: BatchSize) -> u32 {
+        batch_size.get()
+    }
+}
+
+impl fmt::Display for BatchSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    
+    #[test]
+    fn energy_converts_between_joules_and_watt_hours() {
+        assert_eq!(Energy::watt_hours(1.0).as_joules(), 3600.0);
+        assert_eq!(Energy::kilowatt_hours(1.0).as_watt_hours(), 1000.0);
+        assert_eq!(Energy::joules(7.2e6).as_kilowatt_hours(), 2.0);
+        assert_eq!(Energy::joules(1800.0).as_watt_hours(), 0.5);
+    }
+    
+    #[test]
+    fn energies_add_and_display_in_joules() {
+        assert_eq!(Energy::joules(1.5) + Energy::watt_hours(1.0), Energy::joules(3601.5));
+        assert_eq!(Energy::joules(2.5).to_string(), "2.5 J");
+        assert_eq!(Energy::default(), Energy::joules(0.0));
+    }
+    
+    #[test]
+    fn zero_batch_sizes_are_rejected() {
+        assert_eq!(BatchSize::try_from(0), Err(OptimizerError::ZeroBatchSize));
+        assert_eq!(BatchSize::new(0), None);
+        let batch_size = BatchSize::try_from(32).unwrap();
+        assert_eq!((batch_size.get(), u32::from(batch_size)), (32, 32));
+        assert_eq!(batch_size.to_string(), "32");
+    }
+}
@@ -0,0 +1,73 @@
+// JavaScript bindings (`wasm` feature) for wasm32-unknown-unknown via wasm-bindgen:
+// an `Optimizer` class with energyConsumption and optimizeBatchSize
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::model::{ModelParams, Precision};
+use crate::optimizer::Optimizer;
+
+fn js_error(error: impl core::fmt::Display) -> JsError {
+    JsError::new(&error.to_string())
+}
+
+fn parse_precision(precision: &str) -> Result<Precision, JsError> {
+    precision.parse().map_err(js_error)
+}
+
+#[wasm_bindgen(js_name = Optimizer)]
+pub struct WasmOptimizer {
+    inner: Optimizer,
+}
+
+#[wasm_bindgen(js_class = Optimizer)]
+impl WasmOptimizer {
+    // Optimizer with the ModelParams defaults
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmOptimizer {
+        WasmOptimizer { inner: Optimizer::new(ModelParams::default()).expect("default parameters are valid") }
+    }
+    
+    // Optimizer for explicit hardware parameters, in ModelParams field order
+    #[wasm_bindgen(js_name = withParams)]
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_params(base_power_consumption: f64, computation_factor: f64, memory_usage: f64,
+                       memory_power_factor: f64, inference_time: f64, thermal_design_power: f64,
+                       cache_size: f64, memory_bandwidth: f64) -> Result<WasmOptimizer, JsError> {
+        let params = ModelParams::builder()
+            .base_power_consumption(base_power_consumption)
+            .computation_factor(computation_factor)
+            .memory_usage(memory_usage)
+            .memory_power_factor(memory_power_factor)
+            .inference_time(inference_time)
+            .thermal_design_power(thermal_design_power)
+            .cache_size(cache_size)
+            .memory_bandwidth(memory_bandwidth)
+            .build()
+            .map_err(js_error)?;
+        Ok(WasmOptimizer { inner: Optimizer::new(params).map_err(js_error)? })
+    }
+    
+    // Joules for one batch; precision by name, e.g. "fp16"
+    #[wasm_bindgen(js_name = energyConsumption)]
+    pub fn energy_consumption(&self, batch_size: u32, precision: &str) -> Result<f64, JsError> {
+        self.inner.energy_consumption(batch_size, parse_precision(precision)?).map_err(js_error)
+    }
+    
+    // [batchSize, efficiency] of the most efficient batch in the range
+    #[wasm_bindgen(js_name = optimizeBatchSize)]
+    pub fn optimize_batch_size(&self, precision: &str, min_batch: u32, max_batch: u32) -> Result<Vec<f64>, JsError> {
+        let (batch_size, efficiency) = self.inner
+            .optimize_batch_size(parse_precision(precision)?, min_batch, max_batch)
+            .map_err(js_error)?;
+        Ok(vec![batch_size as f64, efficiency])
+    }
+}
+
+impl Default for WasmOptimizer {
+    fn default() -> Self {
+        WasmOptimizer::new()
+    }
+}
@@ -0,0 +1,82 @@
+use std::fs;
+
+use ai_energy::{ModelParams, Optimizer, Precision};
+
+// Regression snapshot of the energy formulas: evaluates energy_consumption and
+// advanced_energy_formula over a fixed grid and compares against the checked-in
+// snapshots/energy_formulas.csv. Run with BLESS=1 to regenerate it after an
+// intentional formula change, then review the diff.
+const SNAPSHOT_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/snapshots/energy_formulas.csv");
+const BATCH_SIZES: [u32; 10] = [1, 2, 4, 8, 16, 32, 64, 128, 256, 1024];
+const TEMPERATURES_K: [f64; 3] = [250.0, 300.0, 350.0];
+
+// Platform libm implementations may differ in the last bits of exp/ln/powf
+const RELATIVE_TOLERANCE: f64 = 1e-12;
+
+#[test]
+fn energy_formulas_match_the_snapshot() {
+    let current = snapshot(&Optimizer::new(ModelParams::default()).unwrap());
+    
+    if std::env::var_os("BLESS").is_some() {
+        fs::write(SNAPSHOT_PATH, &current).unwrap();
+        return;
+    }
+    
+    let expected = fs::read_to_string(SNAPSHOT_PATH).unwrap();
+    let mismatches = compare(&expected, &current);
+    assert!(
+        mismatches.is_empty(),
+        "{}\n{} rows differ; rerun with BLESS=1 if the change is intended",
+        mismatches.join("\n"),
+        mismatches.len()
+    );
+}
+
+// One row per (formula, batch, precision, temperature); energy_consumption has no
+// temperature input and records it as 0
+fn snapshot(optimizer: &Optimizer) -> String {
+    let mut csv = String::from("formula,batch_size,precision,temperature_k,joules\n");
+    for batch_size in BATCH_SIZES {
+        for precision in Precision::ALL {
+            let energy = optimizer.energy_consumption(batch_size, precision).unwrap();
+            csv.push_str(&format!("energy_consumption,{},{},0,{}\n", batch_size, precision.as_str(), energy));
+        }
+    }
+    for temperature in TEMPERATURES_K {
+        for batch_size in BATCH_SIZES {
+            for precision in Precision::ALL {
+                let energy = optimizer.advanced_energy_formula(batch_size, precision, temperature).unwrap();
+                csv.push_str(&format!("advanced_energy_formula,{},{},{},{}\n", batch_size, precision.as_str(), temperature, energy));
+            }
+        }
+    }
+    csv
+}
+
+// Rows whose key columns or value disagree, as "expected ... / actual ..." lines
+fn compare(expected: &str, actual: &str) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    let expected_rows: Vec<&str> = expected.lines().collect();
+    let actual_rows: Vec<&str> = actual.lines().collect();
+    if expected_rows.len() != actual_rows.len() {
+        mismatches.push(format!("expected {} rows, got {}", expected_rows.len(), actual_rows.len()));
+    }
+    for (want, got) in expected_rows.iter().zip(&actual_rows) {
+        if !rows_match(want, got) {
+            mismatches.push(format!("expected {}\n  actual {}", want, got));
+        }
+    }
+    mismatches
+}
+
+fn rows_match(want: &str, got: &str) -> bool {
+    let (want_key, want_value) = want.rsplit_once(',').unwrap_or((want, ""));
+    let (got_key, got_value) = got.rsplit_once(',').unwrap_or((got, ""));
+    if want_key != got_key {
+        return false;
+    }
+    match (want_value.parse::<f64>(), got_value.parse::<f64>()) {
+        (Ok(want), Ok(got)) => want == got || (want - got).abs() <= RELATIVE_TOLERANCE * want.abs().max(got.abs()),
+        _ => want_value == got_value,
+    }
+}
@@ -31,14 +31,14 @@ def check_dependencies():
 def compile_and_run_rust():
     """Compile and run the Rust code to generate the CSV data"""
     print("Compiling Rust code...")
-    result = subprocess.run(["rustc", "ai_energy_lib.rs"], capture_output=True, text=True)
+    result = subprocess.run(["cargo", "build", "--release", "--example", "demo"], capture_output=True, text=True)
     if result.returncode != 0:
         print("Compilation error:")
         print(result.stderr)
         return False
     
     print("Running Rust code to generate data...")
-    result = subprocess.run(["ai_energy_lib.exe"], capture_output=True, text=True)
+    result = subprocess.run(["cargo", "run", "--release", "--quiet", "--example", "demo"], capture_output=True, text=True)
     if result.returncode != 0:
         print("Runtime error:")
         print(result.stderr)
@@ -67,6 +67,7 @@ def visualize_data():
     ax1.plot(data['batch_size'], data['fp32_energy'], label='FP32')
     ax1.plot(data['batch_size'], data['fp16_energy'], label='FP16')
     ax1.plot(data['batch_size'], data['int8_energy'], label='INT8')
+    ax1.plot(data['batch_size'], data['int4_energy'], label='INT4')
     ax1.set_title('Energy Consumption vs Batch Size')
     ax1.set_xlabel('Batch Size')
     ax1.set_ylabel('Energy Consumption (joules)')
@@ -77,6 +78,7 @@ def visualize_data():
     ax2.plot(data['batch_size'], data['fp32_efficiency'], label='FP32')
     ax2.plot(data['batch_size'], data['fp16_efficiency'], label='FP16')
     ax2.plot(data['batch_size'], data['int8_efficiency'], label='INT8')
+    ax2.plot(data['batch_size'], data['int4_efficiency'], label='INT4')
     ax2.set_title('Efficiency vs Batch Size')
     ax2.set_xlabel('Batch Size')
     ax2.set_ylabel('Efficiency (samples/joule)')
@@ -84,7 +86,7 @@ def visualize_data():
     ax2.grid(True)
     
     # Find optimal batch sizes
-    for precision in ['fp32', 'fp16', 'int8']:
+    for precision in ['fp32', 'fp16', 'int8', 'int4']:
         energy_col = f'{precision}_energy'
         efficiency_col = f'{precision}_efficiency'
         